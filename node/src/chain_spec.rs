@@ -1,12 +1,17 @@
 use bein_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY, FeelessConfig, EVMConfig, EthereumConfig
+	opaque::SessionKeys, AccountId, AuraConfig, Balance, BalancesConfig, BlockNumber,
+	CouncilMembershipConfig, GenesisConfig, GrandpaConfig, SessionConfig, Signature,
+	SystemConfig, TechnicalMembershipConfig, ValidatorSetConfig, VestingConfig, WASM_BINARY,
+	BaseFeeConfig, BaseFeeTargetConfig, EVMChainIdConfig, EVMConfig, EthereumConfig, DAYS,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{sr25519, Pair, Public, H160, U256};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
-use sp_runtime::traits::{IdentifyAccount, Verify};
+use sp_runtime::{
+	traits::{IdentifyAccount, Verify},
+	Permill,
+};
 use std::{collections::BTreeMap, str::FromStr};
 
 pub fn bein_properties() -> sc_service::Properties {
@@ -40,9 +45,42 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Balance of every pre-funded test account.
+const ENDOWMENT: Balance = 1 << 70;
+
+/// EIP-155 chain id of the development chain.
+const DEV_CHAIN_ID: u64 = 111121;
+
+/// EIP-155 chain id of the local testnet, distinct from the development chain so that wallets
+/// do not replay transactions between them.
+const LOCAL_TESTNET_CHAIN_ID: u64 = 111122;
+
+/// EIP-1559 base fee at genesis, 50 gwei: a plain 21_000 gas transfer costs about as much as a
+/// native balance transfer.
+const INITIAL_BASE_FEE_PER_GAS: u128 = 50_000_000_000;
+
+/// Largest change of the base fee from one block to the next, as in Ethereum.
+const BASE_FEE_ELASTICITY: Permill = Permill::from_parts(125_000);
+
+/// Block fullness the base fee aims for at genesis, as in Ethereum: half of a block that may
+/// hold up to twice the target.
+const BASE_FEE_TARGET_FULLNESS: Permill = Permill::from_percent(25);
+
+/// A vesting schedule at genesis: account, first vesting block, vesting length in blocks and
+/// amount liquid from the start. The rest of the account balance unlocks linearly.
+type GenesisVesting = (AccountId, BlockNumber, BlockNumber, Balance);
+
+/// Generate a validator account and its Aura and Grandpa session keys.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
+	(
+		get_account_id_from_seed::<sr25519::Public>(s),
+		get_from_seed::<AuraId>(s),
+		get_from_seed::<GrandpaId>(s),
+	)
+}
+
+fn session_keys(aura: AuraId, grandpa: GrandpaId) -> SessionKeys {
+	SessionKeys { aura, grandpa }
 }
 
 pub fn development_config() -> Result<ChainSpec, String> {
@@ -59,8 +97,8 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				wasm_binary,
 				// Initial PoA authorities
 				vec![authority_keys_from_seed("Alice")],
-				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				// Initial council members
+				vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
 				// Pre-funded accounts
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
@@ -68,6 +106,15 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Vesting schedules
+				vec![(
+					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
+					0,
+					30 * DAYS,
+					ENDOWMENT / 10,
+				)],
+				// EVM chain id
+				DEV_CHAIN_ID,
 				true,
 			)
 		},
@@ -98,8 +145,11 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				wasm_binary,
 				// Initial PoA authorities
 				vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
-				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				// Initial council members
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+				],
 				// Pre-funded accounts
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
@@ -115,6 +165,24 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Vesting schedules: a team allocation unlocking over a year after a six month
+				// cliff, and a token sale allocation with a quarter liquid at launch.
+				vec![
+					(
+						get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
+						180 * DAYS,
+						365 * DAYS,
+						0,
+					),
+					(
+						get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
+						0,
+						180 * DAYS,
+						ENDOWMENT / 4,
+					),
+				],
+				// EVM chain id
+				LOCAL_TESTNET_CHAIN_ID,
 				true,
 			)
 		},
@@ -134,9 +202,11 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
-	root_key: AccountId,
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
+	council_members: Vec<AccountId>,
 	endowed_accounts: Vec<AccountId>,
+	vesting: Vec<GenesisVesting>,
+	chain_id: u64,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -146,19 +216,36 @@ fn testnet_genesis(
 			changes_trie_config: Default::default(),
 		},
 		balances: BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 70)).collect(),
+			// Configure endowed accounts with initial balance of 1 << 70.
+			balances: endowed_accounts.iter().cloned().map(|k| (k, ENDOWMENT)).collect(),
+		},
+		vesting: VestingConfig { vesting },
+		validator_set: ValidatorSetConfig {
+			validators: initial_authorities.iter().map(|x| x.0.clone()).collect(),
 		},
-		aura: AuraConfig {
-			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
+		session: SessionConfig {
+			keys: initial_authorities
+				.iter()
+				.map(|x| (x.0.clone(), x.0.clone(), session_keys(x.1.clone(), x.2.clone())))
+				.collect(),
 		},
-		grandpa: GrandpaConfig {
-			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+		// Aura and Grandpa authorities are set by `pallet_session` from the keys above.
+		aura: AuraConfig { authorities: vec![] },
+		grandpa: GrandpaConfig { authorities: vec![] },
+		treasury: Default::default(),
+		council: Default::default(),
+		council_membership: CouncilMembershipConfig {
+			// Council members govern root through the collective and democracy pallets.
+			members: council_members.clone(),
+			phantom: Default::default(),
 		},
-		sudo: SudoConfig {
-			// Assign network admin rights.
-			key: root_key.clone(),
+		technical_committee: Default::default(),
+		technical_membership: TechnicalMembershipConfig {
+			// Council members start as the technical committee, which can pause calls.
+			members: council_members,
+			phantom: Default::default(),
 		},
+		democracy: Default::default(),
 		feeless: Default::default(),
 		evm: EVMConfig {
 			accounts: {
@@ -194,8 +281,13 @@ fn testnet_genesis(
 				map
 			},
 		},
+		evm_chain_id: EVMChainIdConfig { chain_id },
 		ethereum: EthereumConfig {},
-		dynamic_fee: Default::default(),
-		base_fee: Default::default(),
+		base_fee: BaseFeeConfig::new(
+			U256::from(INITIAL_BASE_FEE_PER_GAS),
+			true,
+			BASE_FEE_ELASTICITY,
+		),
+		base_fee_target: BaseFeeTargetConfig { target_fullness: BASE_FEE_TARGET_FULLNESS },
 	}
 }
@@ -40,9 +40,9 @@ pub struct RunCmd {
 	#[structopt(long, default_value = "10000")]
 	pub max_past_logs: u32,
 
-	/// The dynamic-fee pallet target gas price set by block author
-	#[structopt(long, default_value = "1")]
-	pub target_gas_price: u64,
+	/// Number of blocks kept in the cache behind `eth_feeHistory`.
+	#[structopt(long, default_value = "2048")]
+	pub fee_history_limit: u64,
 }
 
 #[derive(Debug, StructOpt)]
@@ -82,4 +82,12 @@ pub enum Subcommand {
 	/// The custom benchmark subcommmand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Try some command against runtime state.
+	#[cfg(feature = "try-runtime")]
+	TryRuntime(try_runtime_cli::TryRuntimeCmd),
+
+	/// Try some command against runtime state. Note: `try-runtime` feature must be enabled.
+	#[cfg(not(feature = "try-runtime"))]
+	TryRuntime,
 }
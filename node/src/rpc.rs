@@ -6,7 +6,7 @@ use fc_rpc::{
 	EthBlockDataCache, OverrideHandle, RuntimeApiStorageOverride, SchemaV1Override,
 	SchemaV2Override, StorageOverride,
 };
-use fc_rpc_core::types::FilterPool;
+use fc_rpc_core::types::{FeeHistoryCache, FilterPool};
 use bein_runtime::{opaque::Block, AccountId, Balance, Hash, Index};
 use jsonrpc_pubsub::manager::SubscriptionManager;
 use pallet_ethereum::EthereumStorageSchema;
@@ -60,11 +60,45 @@ pub struct FullDeps<C, P, A: ChainApi> {
 	pub backend: Arc<fc_db::Backend<Block>>,
 	/// Maximum number of logs in a query.
 	pub max_past_logs: u32,
+	/// Ethereum storage readers, by storage schema.
+	pub overrides: Arc<OverrideHandle<Block>>,
+	/// Number of blocks kept in the fee history cache.
+	pub fee_history_limit: u64,
+	/// Fee history cache, filled by the fee history task of the service.
+	pub fee_history_cache: FeeHistoryCache,
 	/// Manual seal command sink
 	pub command_sink:
 	Option<futures::channel::mpsc::Sender<sc_consensus_manual_seal::rpc::EngineCommand<Hash>>>,
 }
 
+/// Readers of the Ethereum storage of each schema, falling back to the runtime API.
+pub fn overrides_handle<C, BE>(client: Arc<C>) -> Arc<OverrideHandle<Block>>
+	where
+		BE: Backend<Block> + 'static,
+		BE::State: StateBackend<BlakeTwo256>,
+		C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
+		C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+		C: Send + Sync + 'static,
+		C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+{
+	let mut overrides_map = BTreeMap::new();
+	overrides_map.insert(
+		EthereumStorageSchema::V1,
+		Box::new(SchemaV1Override::new(client.clone()))
+			as Box<dyn StorageOverride<_> + Send + Sync>,
+	);
+	overrides_map.insert(
+		EthereumStorageSchema::V2,
+		Box::new(SchemaV2Override::new(client.clone()))
+			as Box<dyn StorageOverride<_> + Send + Sync>,
+	);
+
+	Arc::new(OverrideHandle {
+		schemas: overrides_map,
+		fallback: Box::new(RuntimeApiStorageOverride::new(client)),
+	})
+}
+
 /// Instantiate all Full RPC extensions.
 pub fn create_full<C, P, BE, A>(
 	deps: FullDeps<C, P, A>,
@@ -104,6 +138,9 @@ pub fn create_full<C, P, BE, A>(
 		command_sink,
 		backend,
 		max_past_logs,
+		overrides,
+		fee_history_limit,
+		fee_history_cache,
 		enable_dev_signer,
 	} = deps;
 
@@ -120,23 +157,6 @@ pub fn create_full<C, P, BE, A>(
 	if enable_dev_signer {
 		signers.push(Box::new(EthDevSigner::new()) as Box<dyn EthSigner>);
 	}
-	let mut overrides_map = BTreeMap::new();
-	overrides_map.insert(
-		EthereumStorageSchema::V1,
-		Box::new(SchemaV1Override::new(client.clone()))
-			as Box<dyn StorageOverride<_> + Send + Sync>,
-	);
-	overrides_map.insert(
-		EthereumStorageSchema::V2,
-		Box::new(SchemaV2Override::new(client.clone()))
-			as Box<dyn StorageOverride<_> + Send + Sync>,
-	);
-
-	let overrides = Arc::new(OverrideHandle {
-		schemas: overrides_map,
-		fallback: Box::new(RuntimeApiStorageOverride::new(client.clone())),
-	});
-
 	let block_data_cache = Arc::new(EthBlockDataCache::new(50, 50));
 
 	io.extend_with(EthApiServer::to_delegate(EthApi::new(
@@ -151,6 +171,8 @@ pub fn create_full<C, P, BE, A>(
 		is_authority,
 		max_past_logs,
 		block_data_cache.clone(),
+		fee_history_limit,
+		fee_history_cache,
 	)));
 
 	if let Some(filter_pool) = filter_pool {
@@ -7,7 +7,7 @@ use async_trait::async_trait;
 use fc_consensus::FrontierBlockImport;
 use fc_mapping_sync::{MappingSyncWorker, SyncStrategy};
 use fc_rpc::EthTask;
-use fc_rpc_core::types::FilterPool;
+use fc_rpc_core::types::{FeeHistoryCache, FilterPool};
 use bein_runtime::{self, opaque::Block, RuntimeApi, SLOT_DURATION};
 use futures::StreamExt;
 use sc_cli::SubstrateCli;
@@ -23,7 +23,6 @@ use sc_service::{error::Error as ServiceError, BasePath, Configuration, TaskMana
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sp_consensus::SlotData;
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
-use sp_core::U256;
 use sp_inherents::{InherentData, InherentIdentifier};
 use std::{
 	cell::RefCell,
@@ -121,6 +120,8 @@ pub fn open_frontier_backend(config: &Configuration) -> Result<Arc<fc_db::Backen
 	)?))
 }
 
+// The import queue of Aura needs nothing from the command line.
+#[cfg_attr(feature = "aura", allow(unused_variables))]
 pub fn new_partial(
 	config: &Configuration,
 	cli: &Cli,
@@ -236,7 +237,6 @@ pub fn new_partial(
 			);
 
 			let slot_duration = sc_consensus_aura::slot_duration(&*client)?.slot_duration();
-			let target_gas_price = cli.run.target_gas_price;
 
 			let import_queue =
 				sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _, _>(ImportQueueParams {
@@ -252,10 +252,7 @@ pub fn new_partial(
 								slot_duration,
 							);
 
-						let dynamic_fee =
-							pallet_dynamic_fee::InherentDataProvider(U256::from(target_gas_price));
-
-						Ok((timestamp, slot, dynamic_fee))
+						Ok((timestamp, slot))
 					},
 					spawner: &task_manager.spawn_essential_handle(),
 					can_author_with: sp_consensus::CanAuthorWithNativeVersion::new(
@@ -371,8 +368,14 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 	let subscription_task_executor =
 		sc_rpc::SubscriptionTaskExecutor::new(task_manager.spawn_handle());
 
+	let overrides = crate::rpc::overrides_handle(client.clone());
+	let fee_history_cache: FeeHistoryCache = Arc::new(Mutex::new(BTreeMap::new()));
+	let fee_history_limit = cli.run.fee_history_limit;
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
+		let overrides = overrides.clone();
+		let fee_history_cache = fee_history_cache.clone();
 		let pool = transaction_pool.clone();
 		let network = network.clone();
 		let filter_pool = filter_pool.clone();
@@ -391,6 +394,9 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 				filter_pool: filter_pool.clone(),
 				backend: frontier_backend.clone(),
 				max_past_logs,
+				overrides: overrides.clone(),
+				fee_history_limit,
+				fee_history_cache: fee_history_cache.clone(),
 				command_sink: Some(command_sink.clone()),
 			};
 
@@ -448,6 +454,17 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 		),
 	);
 
+	// Keeps the base fee, gas usage and rewards of recent blocks for `eth_feeHistory`.
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-fee-history",
+		EthTask::fee_history_task(
+			Arc::clone(&client),
+			Arc::clone(&overrides),
+			fee_history_cache,
+			fee_history_limit,
+		),
+	);
+
 	#[cfg(feature = "manual-seal")]
 		{
 			let (block_import, sealing) = consensus_result;
@@ -461,8 +478,6 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 					telemetry.as_ref().map(|x| x.handle()),
 				);
 
-				let target_gas_price = cli.run.target_gas_price;
-
 				// Background authorship future
 				match sealing {
 					Sealing::Manual => {
@@ -476,13 +491,7 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 								select_chain,
 								consensus_data_provider: None,
 								create_inherent_data_providers: move |_, ()| async move {
-									let mock_timestamp = MockTimestampInherentDataProvider;
-
-									let dynamic_fee = pallet_dynamic_fee::InherentDataProvider(
-										U256::from(target_gas_price),
-									);
-
-									Ok((mock_timestamp, dynamic_fee))
+									Ok(MockTimestampInherentDataProvider)
 								},
 							});
 						// we spawn the future on a background thread managed by service.
@@ -500,13 +509,7 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 								select_chain,
 								consensus_data_provider: None,
 								create_inherent_data_providers: move |_, ()| async move {
-									let mock_timestamp = MockTimestampInherentDataProvider;
-
-									let dynamic_fee = pallet_dynamic_fee::InherentDataProvider(
-										U256::from(target_gas_price),
-									);
-
-									Ok((mock_timestamp, dynamic_fee))
+									Ok(MockTimestampInherentDataProvider)
 								},
 							});
 						// we spawn the future on a background thread managed by service.
@@ -537,7 +540,6 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 
 				let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
 				let raw_slot_duration = slot_duration.slot_duration();
-				let target_gas_price = cli.run.target_gas_price;
 
 				let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _, _>(
 					StartAuraParams {
@@ -555,10 +557,7 @@ pub fn new_full(mut config: Configuration, cli: &Cli) -> Result<TaskManager, Ser
 									raw_slot_duration,
 								);
 
-							let dynamic_fee =
-								pallet_dynamic_fee::InherentDataProvider(U256::from(target_gas_price));
-
-							Ok((timestamp, slot, dynamic_fee))
+							Ok((timestamp, slot))
 						},
 						force_authoring,
 						backoff_authoring_blocks,
@@ -0,0 +1,533 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! NFT auction house.
+//!
+//! Owners of a `pallet_bein_nft` item can put it up for auction, which locks the item until
+//! the auction ends:
+//!
+//! - English auctions take increasing bids. Every bid is reserved from the bidder and the
+//!   previous best bid is released. A bid in the last `AntiSnipingPeriod` blocks pushes the
+//!   end back so that others can answer it.
+//! - Dutch auctions lower the price linearly from a start price to an end price. The first
+//!   bid at or above the current price buys the item at that price.
+//!
+//! Auctions are settled in `on_initialize` from their end block on, at most
+//! `MaxSettlementsPerBlock` per block; the rest carry over to the next blocks. A settlement
+//! either completes or changes nothing: a failed one emits `SettlementFailed` and leaves the
+//! auction for anyone to settle with `settle_auction`. Every sale pays the collection
+//! royalty, as it was when the auction was created, to its creator and the rest to the
+//! seller. Bids are plain calls, so stakers can place them through
+//! `pallet_feeless::feeless_call`.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	ensure,
+	pallet_prelude::*,
+	storage::with_transaction,
+	traits::{BalanceStatus, Currency, ExistenceRequirement, ReservableCurrency},
+	transactional,
+	weights::Weight,
+	RuntimeDebug,
+};
+use frame_system::{ensure_signed, pallet_prelude::*};
+use pallet_bein_nft::{CollectionId, ItemId, NonFungibles};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{SaturatedConversion, Saturating, Zero},
+	DispatchError, DispatchResult, Perbill, TransactionOutcome,
+};
+use sp_std::vec::Vec;
+
+pub type AuctionId = u32;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+pub trait WeightInfo {
+	fn create_auction() -> Weight;
+	fn cancel_auction() -> Weight;
+	fn bid() -> Weight;
+	fn settle() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn create_auction() -> Weight {
+		0
+	}
+	fn cancel_auction() -> Weight {
+		0
+	}
+	fn bid() -> Weight {
+		0
+	}
+	fn settle() -> Weight {
+		0
+	}
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum AuctionKind<Balance> {
+	/// Ascending bids, starting at `reserve_price`.
+	English { reserve_price: Balance },
+	/// Price falling from `start_price` to `end_price` over the auction.
+	Dutch { start_price: Balance, end_price: Balance },
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct Auction<AccountId, Balance, BlockNumber> {
+	pub seller: AccountId,
+	pub collection: CollectionId,
+	pub item: ItemId,
+	pub kind: AuctionKind<Balance>,
+	pub start: BlockNumber,
+	pub end: BlockNumber,
+	/// Best English bid so far. Its amount is reserved from the bidder.
+	pub best_bid: Option<(AccountId, Balance)>,
+	/// Collection creator and royalty rate when the auction was created.
+	pub royalty: Option<(AccountId, Perbill)>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency bids are made in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The NFTs that can be auctioned.
+		type Nfts: NonFungibles<Self::AccountId>;
+
+		/// Shortest allowed auction, in blocks.
+		#[pallet::constant]
+		type MinDuration: Get<Self::BlockNumber>;
+
+		/// Longest allowed auction, in blocks.
+		#[pallet::constant]
+		type MaxDuration: Get<Self::BlockNumber>;
+
+		/// A bid placed this close to the end of an English auction extends it to this many
+		/// blocks after the bid.
+		#[pallet::constant]
+		type AntiSnipingPeriod: Get<Self::BlockNumber>;
+
+		/// How much a bid must exceed the previous one by, relative to it.
+		#[pallet::constant]
+		type MinBidIncrement: Get<Perbill>;
+
+		/// Most auctions settled in `on_initialize` of one block.
+		#[pallet::constant]
+		type MaxSettlementsPerBlock: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_auction_id)]
+	pub type NextAuctionId<T: Config> = StorageValue<_, AuctionId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		AuctionId,
+		Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+	>;
+
+	/// Running auction of each item.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_of)]
+	pub type AuctionOf<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, ItemId, AuctionId>;
+
+	/// Auctions to settle at the start of each block.
+	#[pallet::storage]
+	pub type AuctionEndings<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, AuctionId, (), OptionQuery>;
+
+	/// Earliest block whose endings may not all be settled yet.
+	#[pallet::storage]
+	pub type SettlementCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An item was put up for auction. \[auction, seller, collection, item, end\]
+		AuctionCreated(AuctionId, T::AccountId, CollectionId, ItemId, T::BlockNumber),
+		/// An auction without bids was cancelled by its seller. \[auction\]
+		AuctionCancelled(AuctionId),
+		/// A bid was placed on an English auction. \[auction, bidder, amount\]
+		BidPlaced(AuctionId, T::AccountId, BalanceOf<T>),
+		/// A late bid pushed back the end of an auction. \[auction, end\]
+		AuctionExtended(AuctionId, T::BlockNumber),
+		/// An auctioned item was sold. \[auction, buyer, price\]
+		AuctionSold(AuctionId, T::AccountId, BalanceOf<T>),
+		/// An auction ended without a buyer and the item was released. \[auction\]
+		AuctionUnsold(AuctionId),
+		/// Royalty was paid to the collection creator on a sale. \[auction, creator, amount\]
+		RoyaltyPaid(AuctionId, T::AccountId, BalanceOf<T>),
+		/// Settling an ended auction failed and was undone. \[auction, error\]
+		SettlementFailed(AuctionId, DispatchError),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Auction does not exist
+		UnknownAuction,
+		/// Item or collection does not exist
+		UnknownItem,
+		/// Origin may not perform this operation
+		NoPermission,
+		/// Item is already up for auction
+		AlreadyAuctioned,
+		/// Duration is outside `MinDuration..=MaxDuration`
+		BadDuration,
+		/// Dutch auction start price is not above its end price
+		BadPrice,
+		/// Auction has ended
+		AuctionEnded,
+		/// Bid is below the reserve price, the current price or the minimum increment
+		BidTooLow,
+		/// Auction already has bids
+		HasBids,
+		/// No auction ids left
+		NoAvailableAuctionId,
+		/// Auction has not ended yet
+		AuctionNotEnded,
+		/// Winning bid is no longer fully reserved
+		BidUnavailable,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let max = T::MaxSettlementsPerBlock::get();
+			let mut settled: u32 = 0;
+			let mut scanned: u32 = 0;
+			let mut block = SettlementCursor::<T>::get();
+			if block.is_zero() {
+				block = now;
+			}
+			// Both the endings settled and the blocks looked at are capped, so a backlog
+			// or a cursor lagging behind catches up over the following blocks.
+			while block <= now && scanned < max {
+				scanned += 1;
+				let due: Vec<AuctionId> = AuctionEndings::<T>::iter_prefix(block)
+					.map(|(id, ())| id)
+					.take((max - settled) as usize)
+					.collect();
+				for id in due {
+					AuctionEndings::<T>::remove(block, id);
+					if let Some(auction) = Self::auctions(id) {
+						Self::try_settle(id, &auction);
+					}
+					settled += 1;
+				}
+				if settled >= max {
+					break
+				}
+				block = block.saturating_add(1u32.into());
+			}
+			SettlementCursor::<T>::put(block);
+
+			T::DbWeight::get()
+				.reads_writes(1 + scanned as Weight, 1)
+				.saturating_add((settled as Weight).saturating_mul(T::WeightInfo::settle()))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Put an item of the origin up for auction for `duration` blocks.
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: CollectionId,
+			#[pallet::compact] item: ItemId,
+			kind: AuctionKind<BalanceOf<T>>,
+			duration: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let owner = T::Nfts::owner_of(collection, item).ok_or(Error::<T>::UnknownItem)?;
+			ensure!(owner == who, Error::<T>::NoPermission);
+			ensure!(!AuctionOf::<T>::contains_key(collection, item), Error::<T>::AlreadyAuctioned);
+			ensure!(
+				duration >= T::MinDuration::get() && duration <= T::MaxDuration::get(),
+				Error::<T>::BadDuration
+			);
+			if let AuctionKind::Dutch { start_price, end_price } = &kind {
+				ensure!(start_price > end_price, Error::<T>::BadPrice);
+			}
+
+			let id = Self::next_auction_id();
+			let next_id = id.checked_add(1).ok_or(Error::<T>::NoAvailableAuctionId)?;
+
+			T::Nfts::set_locked(collection, item, true)?;
+
+			let start = <frame_system::Pallet<T>>::block_number();
+			let end = start.saturating_add(duration);
+			let royalty = T::Nfts::royalty(collection);
+			NextAuctionId::<T>::put(next_id);
+			Auctions::<T>::insert(
+				id,
+				Auction {
+					seller: who.clone(),
+					collection,
+					item,
+					kind,
+					start,
+					end,
+					best_bid: None,
+					royalty,
+				},
+			);
+			AuctionOf::<T>::insert(collection, item, id);
+			AuctionEndings::<T>::insert(end, id, ());
+			Self::deposit_event(Event::AuctionCreated(id, who, collection, item, end));
+
+			Ok(().into())
+		}
+
+		/// Cancel an auction of the origin that has no bids, releasing the item.
+		#[pallet::weight(T::WeightInfo::cancel_auction())]
+		pub fn cancel_auction(
+			origin: OriginFor<T>,
+			#[pallet::compact] auction_id: AuctionId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let auction = Self::auctions(auction_id).ok_or(Error::<T>::UnknownAuction)?;
+			ensure!(auction.seller == who, Error::<T>::NoPermission);
+			ensure!(auction.best_bid.is_none(), Error::<T>::HasBids);
+
+			T::Nfts::set_locked(auction.collection, auction.item, false)?;
+			Self::remove_auction(auction_id, &auction);
+			Self::deposit_event(Event::AuctionCancelled(auction_id));
+
+			Ok(().into())
+		}
+
+		/// Bid on an auction.
+		///
+		/// On an English auction `amount` is reserved until the bid is outbid or the auction
+		/// ends. On a Dutch auction `amount` is the most the origin will pay: the item is
+		/// bought right away at the current price if it is not above `amount`.
+		#[pallet::weight(T::WeightInfo::bid())]
+		#[transactional]
+		pub fn bid(
+			origin: OriginFor<T>,
+			#[pallet::compact] auction_id: AuctionId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let mut auction = Self::auctions(auction_id).ok_or(Error::<T>::UnknownAuction)?;
+			ensure!(auction.seller != who, Error::<T>::NoPermission);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < auction.end, Error::<T>::AuctionEnded);
+
+			match auction.kind {
+				AuctionKind::English { reserve_price } => {
+					Self::place_bid(auction_id, &mut auction, reserve_price, &who, amount)?;
+
+					let extended_end = now.saturating_add(T::AntiSnipingPeriod::get());
+					if extended_end > auction.end {
+						AuctionEndings::<T>::remove(auction.end, auction_id);
+						AuctionEndings::<T>::insert(extended_end, auction_id, ());
+						auction.end = extended_end;
+						Self::deposit_event(Event::AuctionExtended(auction_id, extended_end));
+					}
+					Auctions::<T>::insert(auction_id, auction);
+				}
+				AuctionKind::Dutch { .. } => {
+					let price = Self::dutch_price(&auction, now);
+					ensure!(amount >= price, Error::<T>::BidTooLow);
+
+					Self::pay(auction_id, &auction, price, |dest, value| {
+						T::Currency::transfer(&who, dest, value, ExistenceRequirement::KeepAlive)
+					})?;
+					T::Nfts::transfer(auction.collection, auction.item, &who)?;
+					Self::remove_auction(auction_id, &auction);
+					Self::deposit_event(Event::AuctionSold(auction_id, who, price));
+				}
+			}
+
+			Ok(().into())
+		}
+
+		/// Settle an auction past its end, such as one whose settlement failed or is still
+		/// waiting in the backlog of `on_initialize`.
+		#[pallet::weight(T::WeightInfo::settle())]
+		#[transactional]
+		pub fn settle_auction(
+			origin: OriginFor<T>,
+			#[pallet::compact] auction_id: AuctionId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let auction = Self::auctions(auction_id).ok_or(Error::<T>::UnknownAuction)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= auction.end, Error::<T>::AuctionNotEnded);
+			Self::settle(auction_id, &auction)?;
+
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Current price of a Dutch auction, falling linearly from its start to its end.
+	pub fn dutch_price(
+		auction: &Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		now: T::BlockNumber,
+	) -> BalanceOf<T> {
+		match auction.kind {
+			AuctionKind::Dutch { start_price, end_price } => {
+				let elapsed = now.saturating_sub(auction.start).saturated_into::<u32>();
+				let duration = auction.end.saturating_sub(auction.start).saturated_into::<u32>();
+				let discount = Perbill::from_rational(elapsed, duration.max(1)) *
+					start_price.saturating_sub(end_price);
+				start_price.saturating_sub(discount)
+			}
+			AuctionKind::English { .. } => Zero::zero(),
+		}
+	}
+
+	/// Smallest bid that would be accepted on an English auction.
+	pub fn minimum_bid(
+		auction: &Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+	) -> BalanceOf<T> {
+		match (&auction.kind, &auction.best_bid) {
+			(_, Some((_, best))) => {
+				let increment = (T::MinBidIncrement::get() * *best).max(1u32.into());
+				best.saturating_add(increment)
+			}
+			(AuctionKind::English { reserve_price }, None) => *reserve_price,
+			(AuctionKind::Dutch { .. }, None) => Zero::zero(),
+		}
+	}
+
+	/// Reserve a new best bid and release the previous one.
+	fn place_bid(
+		auction_id: AuctionId,
+		auction: &mut Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		reserve_price: BalanceOf<T>,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(
+			amount >= reserve_price && amount >= Self::minimum_bid(auction),
+			Error::<T>::BidTooLow
+		);
+
+		match auction.best_bid.take() {
+			// Raising one's own bid only reserves the difference.
+			Some((bidder, previous)) if &bidder == who => {
+				T::Currency::reserve(who, amount.saturating_sub(previous))?;
+			}
+			Some((bidder, previous)) => {
+				T::Currency::reserve(who, amount)?;
+				T::Currency::unreserve(&bidder, previous);
+			}
+			None => {
+				T::Currency::reserve(who, amount)?;
+			}
+		}
+		auction.best_bid = Some((who.clone(), amount));
+		Self::deposit_event(Event::BidPlaced(auction_id, who.clone(), amount));
+
+		Ok(())
+	}
+
+	/// Split `price` between the royalty snapshot of the auction and the seller, moving each
+	/// part with `transfer`.
+	fn pay(
+		auction_id: AuctionId,
+		auction: &Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		price: BalanceOf<T>,
+		mut transfer: impl FnMut(&T::AccountId, BalanceOf<T>) -> DispatchResult,
+	) -> DispatchResult {
+		let mut remaining = price;
+		if let Some((creator, royalty)) = &auction.royalty {
+			let royalty = *royalty * price;
+			if !royalty.is_zero() && creator != &auction.seller {
+				transfer(creator, royalty)?;
+				remaining = remaining.saturating_sub(royalty);
+				Self::deposit_event(Event::RoyaltyPaid(auction_id, creator.clone(), royalty));
+			}
+		}
+		transfer(&auction.seller, remaining)
+	}
+
+	/// Settle an ended auction in a storage transaction. If it fails nothing is changed and
+	/// the auction stays open to `settle_auction`.
+	fn try_settle(
+		auction_id: AuctionId,
+		auction: &Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+	) {
+		let result = with_transaction(|| match Self::settle(auction_id, auction) {
+			Ok(()) => TransactionOutcome::Commit(Ok(())),
+			Err(error) => TransactionOutcome::Rollback(Err(error)),
+		});
+		if let Err(error) = result {
+			Self::deposit_event(Event::SettlementFailed(auction_id, error));
+		}
+	}
+
+	/// Conclude an ended auction. Not atomic on its own: callers run it in a transaction.
+	fn settle(
+		auction_id: AuctionId,
+		auction: &Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+	) -> DispatchResult {
+		match auction.best_bid.clone() {
+			Some((winner, price)) => {
+				Self::pay(auction_id, auction, price, |dest, value| {
+					let missing = T::Currency::repatriate_reserved(
+						&winner,
+						dest,
+						value,
+						BalanceStatus::Free,
+					)?;
+					ensure!(missing.is_zero(), Error::<T>::BidUnavailable);
+					Ok(())
+				})?;
+				T::Nfts::transfer(auction.collection, auction.item, &winner)?;
+				Self::remove_auction(auction_id, auction);
+				Self::deposit_event(Event::AuctionSold(auction_id, winner, price));
+			}
+			None => {
+				T::Nfts::set_locked(auction.collection, auction.item, false)?;
+				Self::remove_auction(auction_id, auction);
+				Self::deposit_event(Event::AuctionUnsold(auction_id));
+			}
+		}
+		Ok(())
+	}
+
+	fn remove_auction(
+		auction_id: AuctionId,
+		auction: &Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+	) {
+		Auctions::<T>::remove(auction_id);
+		AuctionOf::<T>::remove(auction.collection, auction.item);
+		AuctionEndings::<T>::remove(auction.end, auction_id);
+	}
+}
@@ -0,0 +1,176 @@
+use crate as pallet_auction_house;
+use frame_support::parameter_types;
+use frame_system as system;
+use pallet_evm::{AddressMapping, EnsureAddressNever, EnsureAddressRoot};
+use sp_core::{H160, H256};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		EVM: pallet_evm::{Pallet, Call, Storage, Config, Event<T>},
+		BeinNft: pallet_bein_nft::{Pallet, Call, Storage, Event<T>},
+		AuctionHouse: pallet_auction_house::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1;
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+/// The account of an address is its last eight bytes.
+pub struct TruncatedAddressMapping;
+impl AddressMapping<u64> for TruncatedAddressMapping {
+	fn into_account_id(address: H160) -> u64 {
+		let mut account = [0u8; 8];
+		account.copy_from_slice(&address[12..20]);
+		u64::from_be_bytes(account)
+	}
+}
+
+impl pallet_evm::Config for Test {
+	type FeeCalculator = ();
+	type GasWeightMapping = ();
+	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+	type CallOrigin = EnsureAddressRoot<u64>;
+	type WithdrawOrigin = EnsureAddressNever<u64>;
+	type AddressMapping = TruncatedAddressMapping;
+	type Currency = Balances;
+	type Event = Event;
+	type PrecompilesType = ();
+	type PrecompilesValue = ();
+	type ChainId = ();
+	type BlockGasLimit = ();
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type OnChargeTransaction = ();
+	type FindAuthor = ();
+}
+
+parameter_types! {
+	pub const CollectionDeposit: u64 = 10;
+	pub const MaxRoyalty: Perbill = Perbill::from_percent(20);
+	pub const StringLimit: u32 = 8;
+	pub const RemoveItemsLimit: u32 = 2;
+}
+
+impl pallet_bein_nft::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type CollectionDeposit = CollectionDeposit;
+	type MaxRoyalty = MaxRoyalty;
+	type StringLimit = StringLimit;
+	type RemoveItemsLimit = RemoveItemsLimit;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinDuration: u64 = 5;
+	pub const MaxDuration: u64 = 100;
+	pub const AntiSnipingPeriod: u64 = 3;
+	pub const MinBidIncrement: Perbill = Perbill::from_percent(10);
+	pub static MaxSettlementsPerBlock: u32 = 10;
+}
+
+impl pallet_auction_house::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type Nfts = BeinNft;
+	type MinDuration = MinDuration;
+	type MaxDuration = MaxDuration;
+	type AntiSnipingPeriod = AntiSnipingPeriod;
+	type MinBidIncrement = MinBidIncrement;
+	type MaxSettlementsPerBlock = MaxSettlementsPerBlock;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime. Accounts 1 to 4 hold 1_000. Account 1
+// created collection 0 with a 10% royalty and minted items 0 to 2 of it to account 2.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: (1..=4).map(|who| (who, 1_000)).collect() }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		BeinNft::create_collection(
+			Origin::signed(1),
+			b"Bein".to_vec(),
+			b"BIN".to_vec(),
+			Perbill::from_percent(10),
+		)
+		.unwrap();
+		for item in 0..3 {
+			BeinNft::mint(Origin::signed(1), 0, item, 2, vec![]).unwrap();
+		}
+	});
+	ext
+}
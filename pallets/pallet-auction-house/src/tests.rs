@@ -0,0 +1,229 @@
+use crate::{mock::*, AuctionKind, Error, Event as AuctionEvent};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnInitialize, ReservableCurrency},
+};
+use pallet_bein_nft::NonFungibles;
+
+fn english(reserve_price: u64) -> AuctionKind<u64> {
+	AuctionKind::English { reserve_price }
+}
+
+/// Account 2 puts `item` up for auction for 10 blocks.
+fn auction(item: u32, kind: AuctionKind<u64>) {
+	assert_ok!(AuctionHouse::create_auction(Origin::signed(2), 0, item, kind, 10));
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		AuctionHouse::on_initialize(System::block_number());
+	}
+}
+
+fn owner(item: u32) -> Option<u64> {
+	<BeinNft as NonFungibles<u64>>::owner_of(0, item)
+}
+
+fn locked(item: u32) -> bool {
+	BeinNft::items(0, item).unwrap().locked
+}
+
+#[test]
+fn auctioned_item_is_locked() {
+	new_test_ext().execute_with(|| {
+		auction(0, english(100));
+
+		assert!(locked(0));
+		assert_eq!(AuctionHouse::auction_of(0, 0), Some(0));
+		assert_noop!(
+			BeinNft::transfer(Origin::signed(2), 0, 0, 3),
+			pallet_bein_nft::Error::<Test>::Locked
+		);
+		System::assert_last_event(Event::AuctionHouse(AuctionEvent::AuctionCreated(
+			0, 2, 0, 0, 11
+		)));
+	});
+}
+
+#[test]
+fn auction_creation_is_checked() {
+	new_test_ext().execute_with(|| {
+		let create = |who, item, kind, duration| {
+			AuctionHouse::create_auction(Origin::signed(who), 0, item, kind, duration)
+		};
+		assert_noop!(create(3, 0, english(100), 10), Error::<Test>::NoPermission);
+		assert_noop!(create(2, 9, english(100), 10), Error::<Test>::UnknownItem);
+		assert_noop!(create(2, 0, english(100), 4), Error::<Test>::BadDuration);
+		assert_noop!(create(2, 0, english(100), 101), Error::<Test>::BadDuration);
+		let rising = AuctionKind::Dutch { start_price: 100, end_price: 200 };
+		assert_noop!(create(2, 0, rising, 10), Error::<Test>::BadPrice);
+
+		auction(0, english(100));
+		assert_noop!(create(2, 0, english(100), 10), Error::<Test>::AlreadyAuctioned);
+	});
+}
+
+#[test]
+fn english_auction_sells_to_the_best_bidder_and_pays_royalty() {
+	new_test_ext().execute_with(|| {
+		auction(0, english(100));
+		assert_noop!(AuctionHouse::bid(Origin::signed(2), 0, 100), Error::<Test>::NoPermission);
+		assert_noop!(AuctionHouse::bid(Origin::signed(3), 0, 99), Error::<Test>::BidTooLow);
+		assert_ok!(AuctionHouse::bid(Origin::signed(3), 0, 100));
+		assert_eq!(Balances::reserved_balance(3), 100);
+
+		// The next bid must be 10% higher, and releases the one it beats.
+		assert_noop!(AuctionHouse::bid(Origin::signed(4), 0, 109), Error::<Test>::BidTooLow);
+		assert_ok!(AuctionHouse::bid(Origin::signed(4), 0, 110));
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::reserved_balance(4), 110);
+
+		run_to_block(11);
+		assert_eq!(owner(0), Some(4));
+		assert!(!locked(0));
+		assert_eq!(Balances::free_balance(4), 890);
+		assert_eq!(Balances::free_balance(1), 990 + 11);
+		assert_eq!(Balances::free_balance(2), 1_000 + 99);
+		assert!(AuctionHouse::auctions(0).is_none());
+		System::assert_has_event(Event::AuctionHouse(AuctionEvent::RoyaltyPaid(0, 1, 11)));
+		System::assert_last_event(Event::AuctionHouse(AuctionEvent::AuctionSold(0, 4, 110)));
+	});
+}
+
+#[test]
+fn raising_ones_own_bid_reserves_the_difference() {
+	new_test_ext().execute_with(|| {
+		auction(0, english(100));
+		assert_ok!(AuctionHouse::bid(Origin::signed(3), 0, 100));
+		assert_ok!(AuctionHouse::bid(Origin::signed(3), 0, 150));
+
+		assert_eq!(Balances::reserved_balance(3), 150);
+		assert_eq!(Balances::free_balance(3), 850);
+	});
+}
+
+#[test]
+fn late_bid_extends_the_auction() {
+	new_test_ext().execute_with(|| {
+		auction(0, english(100));
+		run_to_block(9);
+		assert_ok!(AuctionHouse::bid(Origin::signed(3), 0, 100));
+		System::assert_last_event(Event::AuctionHouse(AuctionEvent::AuctionExtended(0, 12)));
+
+		run_to_block(11);
+		assert!(AuctionHouse::auctions(0).is_some());
+		run_to_block(12);
+		assert_eq!(owner(0), Some(3));
+		assert_noop!(AuctionHouse::bid(Origin::signed(4), 0, 200), Error::<Test>::UnknownAuction);
+	});
+}
+
+#[test]
+fn dutch_auction_sells_at_the_current_price() {
+	new_test_ext().execute_with(|| {
+		auction(0, AuctionKind::Dutch { start_price: 200, end_price: 100 });
+		run_to_block(6);
+
+		// Halfway, the price is halfway down.
+		assert_noop!(AuctionHouse::bid(Origin::signed(3), 0, 149), Error::<Test>::BidTooLow);
+		assert_ok!(AuctionHouse::bid(Origin::signed(3), 0, 180));
+		assert_eq!(owner(0), Some(3));
+		assert_eq!(Balances::free_balance(3), 850);
+		assert_eq!(Balances::free_balance(1), 990 + 15);
+		assert_eq!(Balances::free_balance(2), 1_000 + 135);
+		System::assert_last_event(Event::AuctionHouse(AuctionEvent::AuctionSold(0, 3, 150)));
+	});
+}
+
+#[test]
+fn auction_without_bids_releases_the_item() {
+	new_test_ext().execute_with(|| {
+		auction(0, english(100));
+		run_to_block(11);
+		assert_eq!(owner(0), Some(2));
+		assert!(!locked(0));
+		assert_eq!(AuctionHouse::auction_of(0, 0), None);
+		System::assert_last_event(Event::AuctionHouse(AuctionEvent::AuctionUnsold(0)));
+	});
+}
+
+#[test]
+fn only_the_seller_cancels_an_auction_without_bids() {
+	new_test_ext().execute_with(|| {
+		auction(0, english(100));
+		assert_noop!(
+			AuctionHouse::cancel_auction(Origin::signed(3), 0),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(AuctionHouse::bid(Origin::signed(3), 0, 100));
+		assert_noop!(AuctionHouse::cancel_auction(Origin::signed(2), 0), Error::<Test>::HasBids);
+
+		auction(1, english(100));
+		assert_ok!(AuctionHouse::cancel_auction(Origin::signed(2), 1));
+		assert!(!locked(1));
+		assert!(AuctionHouse::auctions(1).is_none());
+		System::assert_last_event(Event::AuctionHouse(AuctionEvent::AuctionCancelled(1)));
+	});
+}
+
+#[test]
+fn royalty_is_the_one_of_auction_creation() {
+	new_test_ext().execute_with(|| {
+		auction(0, english(100));
+		assert_ok!(BeinNft::set_royalty(Origin::signed(1), 0, sp_runtime::Perbill::zero()));
+		assert_ok!(AuctionHouse::bid(Origin::signed(3), 0, 100));
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 990 + 10);
+		assert_eq!(Balances::free_balance(2), 1_000 + 90);
+	});
+}
+
+#[test]
+fn settlements_beyond_the_block_limit_carry_over() {
+	new_test_ext().execute_with(|| {
+		MaxSettlementsPerBlock::set(2);
+		for item in 0..3 {
+			auction(item, english(100));
+		}
+
+		run_to_block(11);
+		assert_eq!((0..3).filter(|item| locked(*item)).count(), 1);
+		run_to_block(12);
+		assert!((0..3).all(|item| !locked(item)));
+	});
+}
+
+#[test]
+fn failed_settlement_changes_nothing_and_can_be_retried() {
+	new_test_ext().execute_with(|| {
+		auction(0, english(100));
+		assert_ok!(AuctionHouse::bid(Origin::signed(3), 0, 100));
+		assert_noop!(
+			AuctionHouse::settle_auction(Origin::signed(4), 0),
+			Error::<Test>::AuctionNotEnded
+		);
+		// The winning bid is no longer all there once the auction ends.
+		Balances::unreserve(&3, 50);
+
+		run_to_block(11);
+		assert_eq!(owner(0), Some(2));
+		assert!(locked(0));
+		assert_eq!(Balances::free_balance(1), 990);
+		assert_eq!(Balances::reserved_balance(3), 50);
+		System::assert_last_event(Event::AuctionHouse(AuctionEvent::SettlementFailed(
+			0,
+			Error::<Test>::BidUnavailable.into(),
+		)));
+		assert_noop!(AuctionHouse::bid(Origin::signed(4), 0, 200), Error::<Test>::AuctionEnded);
+		assert_noop!(
+			AuctionHouse::settle_auction(Origin::signed(4), 0),
+			Error::<Test>::BidUnavailable
+		);
+
+		Balances::reserve(&3, 50).unwrap();
+		assert_ok!(AuctionHouse::settle_auction(Origin::signed(4), 0));
+		assert_eq!(owner(0), Some(3));
+	});
+}
@@ -0,0 +1,131 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Block fullness the EIP-1559 base fee aims for.
+//!
+//! The base fee rises after blocks fuller than the target and falls after emptier ones. The
+//! target is set in the genesis config of each chain spec and `SetOrigin` can change it. The
+//! runtime reads it when it gives `pallet_base_fee` its thresholds.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use frame_support::{pallet_prelude::*, weights::Weight};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{traits::Zero, Permill};
+
+pub trait WeightInfo {
+	fn set_target_fullness() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn set_target_fullness() -> Weight {
+		0
+	}
+}
+
+/// Target used until genesis or `SetOrigin` sets one, as in Ethereum: half of the gas limit
+/// of a block that may hold up to twice the target.
+pub const DEFAULT_TARGET_FULLNESS: Permill = Permill::from_percent(25);
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin allowed to change the target fullness.
+		type SetOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::type_value]
+	pub fn DefaultTargetFullness() -> Permill {
+		DEFAULT_TARGET_FULLNESS
+	}
+
+	/// Block fullness the base fee aims for.
+	#[pallet::storage]
+	#[pallet::getter(fn target_fullness)]
+	pub type TargetFullness<T> = StorageValue<_, Permill, ValueQuery, DefaultTargetFullness>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		pub target_fullness: Permill,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self { target_fullness: DEFAULT_TARGET_FULLNESS }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			assert!(Pallet::<T>::is_valid(self.target_fullness), "Invalid target fullness");
+			TargetFullness::<T>::put(self.target_fullness);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event {
+		/// The target fullness was changed. \[target_fullness\]
+		TargetFullnessSet(Permill),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Target is zero or above half of a block
+		InvalidTarget,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Change the block fullness the base fee aims for, from the next block on.
+		#[pallet::weight(T::WeightInfo::set_target_fullness())]
+		pub fn set_target_fullness(
+			origin: OriginFor<T>,
+			target_fullness: Permill,
+		) -> DispatchResultWithPostInfo {
+			T::SetOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_valid(target_fullness), Error::<T>::InvalidTarget);
+
+			TargetFullness::<T>::put(target_fullness);
+			Self::deposit_event(Event::TargetFullnessSet(target_fullness));
+
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// A block must be able to be twice as full as the target, for the base fee to fall as
+	/// fast below the target as it rises above it.
+	pub fn is_valid(target_fullness: Permill) -> bool {
+		!target_fullness.is_zero() && target_fullness <= Permill::from_percent(50)
+	}
+}
+
+impl<T: Config> Get<Permill> for Pallet<T> {
+	fn get() -> Permill {
+		Self::target_fullness()
+	}
+}
@@ -0,0 +1,49 @@
+use crate::{mock::*, Error, Event as TargetEvent, DEFAULT_TARGET_FULLNESS};
+use frame_support::{assert_noop, assert_ok, traits::Get};
+use sp_runtime::{DispatchError, Permill};
+
+#[test]
+fn target_is_set_at_genesis() {
+	new_test_ext(Permill::from_percent(40)).execute_with(|| {
+		assert_eq!(BaseFeeTarget::target_fullness(), Permill::from_percent(40));
+		assert_eq!(<BaseFeeTarget as Get<Permill>>::get(), Permill::from_percent(40));
+	});
+}
+
+#[test]
+fn unset_target_falls_back_to_the_default() {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	sp_io::TestExternalities::from(storage)
+		.execute_with(|| assert_eq!(BaseFeeTarget::target_fullness(), DEFAULT_TARGET_FULLNESS));
+}
+
+#[test]
+fn set_origin_changes_the_target() {
+	new_test_ext(Permill::from_percent(25)).execute_with(|| {
+		assert_noop!(
+			BaseFeeTarget::set_target_fullness(Origin::signed(1), Permill::from_percent(30)),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(BaseFeeTarget::set_target_fullness(Origin::root(), Permill::from_percent(30)));
+		assert_eq!(BaseFeeTarget::target_fullness(), Permill::from_percent(30));
+		System::assert_last_event(Event::BaseFeeTarget(TargetEvent::TargetFullnessSet(
+			Permill::from_percent(30),
+		)));
+	});
+}
+
+#[test]
+fn target_must_leave_room_for_fuller_blocks() {
+	new_test_ext(Permill::from_percent(25)).execute_with(|| {
+		assert_noop!(
+			BaseFeeTarget::set_target_fullness(Origin::root(), Permill::from_percent(0)),
+			Error::<Test>::InvalidTarget
+		);
+		assert_noop!(
+			BaseFeeTarget::set_target_fullness(Origin::root(), Permill::from_percent(51)),
+			Error::<Test>::InvalidTarget
+		);
+		assert_ok!(BaseFeeTarget::set_target_fullness(Origin::root(), Permill::from_percent(50)));
+	});
+}
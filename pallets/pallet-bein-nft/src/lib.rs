@@ -0,0 +1,620 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Creator NFT collections.
+//!
+//! Any account can create a collection by reserving `CollectionDeposit` and then mint items
+//! into it, each with its own metadata URI. Collections carry a royalty, paid to the creator
+//! by marketplaces such as the auction house on every sale. Every collection is reachable
+//! from the EVM at `collection_address(id)`, where the runtime serves it as an ERC-721
+//! precompile, so Substrate wallets and Solidity marketplaces see the same items.
+//!
+//! Once all its items are burned, the owner can `destroy_collection`. It is then frozen and
+//! anyone can clear its holder counts and operator approvals with `destroy_accounts`, at
+//! most `RemoveItemsLimit` per call. The last call removes the collection and returns the
+//! deposit.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	ensure,
+	pallet_prelude::*,
+	traits::{Currency, ReservableCurrency},
+	transactional,
+	weights::Weight,
+	RuntimeDebug,
+};
+use frame_system::{ensure_signed, pallet_prelude::*};
+use scale_info::TypeInfo;
+use sp_core::H160;
+use sp_runtime::{
+	traits::{StaticLookup, Zero},
+	DispatchResult, Perbill,
+};
+use sp_std::vec::Vec;
+
+pub type CollectionId = u32;
+pub type ItemId = u32;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Prefix of the EVM address of every collection, followed by the big-endian collection id.
+pub const COLLECTION_ADDRESS_PREFIX: [u8; 4] = [0xff, 0xff, 0xff, 0xfe];
+
+/// Code stored at collection addresses so that Solidity's `extcodesize` checks pass. It
+/// reverts if ever executed, which cannot happen since the precompile takes precedence.
+pub const COLLECTION_ADDRESS_CODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
+
+pub trait WeightInfo {
+	fn create_collection() -> Weight;
+	fn set_royalty() -> Weight;
+	fn destroy_collection() -> Weight;
+	fn destroy_accounts(n: u32) -> Weight;
+	fn mint() -> Weight;
+	fn burn() -> Weight;
+	fn transfer() -> Weight;
+	fn approve_transfer() -> Weight;
+	fn set_approval_for_all() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn create_collection() -> Weight {
+		0
+	}
+	fn set_royalty() -> Weight {
+		0
+	}
+	fn destroy_collection() -> Weight {
+		0
+	}
+	fn destroy_accounts(_n: u32) -> Weight {
+		0
+	}
+	fn mint() -> Weight {
+		0
+	}
+	fn burn() -> Weight {
+		0
+	}
+	fn transfer() -> Weight {
+		0
+	}
+	fn approve_transfer() -> Weight {
+		0
+	}
+	fn set_approval_for_all() -> Weight {
+		0
+	}
+}
+
+/// Operations other pallets, such as marketplaces, need on NFTs.
+pub trait NonFungibles<AccountId> {
+	fn owner_of(collection: CollectionId, item: ItemId) -> Option<AccountId>;
+
+	/// Royalty recipient and rate of a collection.
+	fn royalty(collection: CollectionId) -> Option<(AccountId, Perbill)>;
+
+	/// Lock or unlock an item. A locked item cannot be transferred by its owner.
+	fn set_locked(collection: CollectionId, item: ItemId, locked: bool) -> DispatchResult;
+
+	/// Move an item to `dest`, ignoring approvals. Unlocks the item.
+	fn transfer(collection: CollectionId, item: ItemId, dest: &AccountId) -> DispatchResult;
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct CollectionDetails<AccountId, Balance> {
+	pub owner: AccountId,
+	pub royalty: Perbill,
+	pub deposit: Balance,
+	pub items: u32,
+	/// Whether the collection is being destroyed. No items or approvals can be added.
+	pub destroying: bool,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+pub struct CollectionMetadata {
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ItemDetails<AccountId> {
+	pub owner: AccountId,
+	/// Account allowed to transfer this item on behalf of the owner.
+	pub approved: Option<AccountId>,
+	/// Locked items are held by a pallet, e.g. during an auction.
+	pub locked: bool,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_evm::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency used for the collection deposit.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Deposit reserved from the creator for the lifetime of a collection.
+		#[pallet::constant]
+		type CollectionDeposit: Get<BalanceOf<Self>>;
+
+		/// Highest royalty a collection may charge.
+		#[pallet::constant]
+		type MaxRoyalty: Get<Perbill>;
+
+		/// Maximum length of a collection name, symbol or item metadata URI.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// Most holder counts and operator approvals removed by one `destroy_accounts` call.
+		#[pallet::constant]
+		type RemoveItemsLimit: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_collection_id)]
+	pub type NextCollectionId<T: Config> = StorageValue<_, CollectionId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn collections)]
+	pub type Collections<T: Config> =
+		StorageMap<_, Twox64Concat, CollectionId, CollectionDetails<T::AccountId, BalanceOf<T>>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn collection_metadata)]
+	pub type CollectionMetadataOf<T: Config> =
+		StorageMap<_, Twox64Concat, CollectionId, CollectionMetadata, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn items)]
+	pub type Items<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		Twox64Concat,
+		ItemId,
+		ItemDetails<T::AccountId>,
+	>;
+
+	/// Metadata URI of each item.
+	#[pallet::storage]
+	#[pallet::getter(fn item_metadata)]
+	pub type ItemMetadataOf<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, ItemId, Vec<u8>, ValueQuery>;
+
+	/// Number of items of a collection held by an account.
+	#[pallet::storage]
+	#[pallet::getter(fn balance_of)]
+	pub type OwnedCount<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery,
+	>;
+
+	/// Operators allowed to transfer every item of an owner, keyed by `(owner, operator)`.
+	#[pallet::storage]
+	#[pallet::getter(fn is_approved_for_all)]
+	pub type OperatorApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId),
+		bool,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A collection was created. \[collection, owner\]
+		CollectionCreated(CollectionId, T::AccountId),
+		/// A collection royalty was changed. \[collection, royalty\]
+		RoyaltySet(CollectionId, Perbill),
+		/// The owner started destroying a collection with no items left. \[collection\]
+		DestructionStarted(CollectionId),
+		/// A collection was destroyed and its deposit returned. \[collection\]
+		CollectionDestroyed(CollectionId),
+		/// An item was minted. \[collection, item, owner\]
+		Minted(CollectionId, ItemId, T::AccountId),
+		/// An item was burned. \[collection, item, owner\]
+		Burned(CollectionId, ItemId, T::AccountId),
+		/// An item was transferred. \[collection, item, from, to\]
+		Transferred(CollectionId, ItemId, T::AccountId, T::AccountId),
+		/// An item transfer approval was set or cleared. \[collection, item, owner, delegate\]
+		ApprovalSet(CollectionId, ItemId, T::AccountId, Option<T::AccountId>),
+		/// An operator approval was changed. \[collection, owner, operator, approved\]
+		ApprovalForAll(CollectionId, T::AccountId, T::AccountId, bool),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Collection does not exist
+		UnknownCollection,
+		/// Item does not exist
+		UnknownItem,
+		/// Item id is already taken in this collection
+		AlreadyExists,
+		/// Origin may not perform this operation
+		NoPermission,
+		/// Royalty is above `MaxRoyalty`
+		RoyaltyTooHigh,
+		/// Name, symbol or metadata is longer than `StringLimit`
+		BadMetadata,
+		/// Collection still has items
+		InUse,
+		/// Item is locked
+		Locked,
+		/// Collection is being destroyed
+		Destroying,
+		/// Collection is not being destroyed
+		NotDestroying,
+		/// No collection ids left
+		NoAvailableCollectionId,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a new collection owned by the origin.
+		#[pallet::weight(<T as Config>::WeightInfo::create_collection())]
+		#[transactional]
+		pub fn create_collection(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			royalty: Perbill,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let limit = T::StringLimit::get() as usize;
+			ensure!(name.len() <= limit && symbol.len() <= limit, Error::<T>::BadMetadata);
+			ensure!(royalty <= T::MaxRoyalty::get(), Error::<T>::RoyaltyTooHigh);
+
+			let id = Self::next_collection_id();
+			let next_id = id.checked_add(1).ok_or(Error::<T>::NoAvailableCollectionId)?;
+
+			let deposit = T::CollectionDeposit::get();
+			<T as Config>::Currency::reserve(&who, deposit)?;
+
+			NextCollectionId::<T>::put(next_id);
+			Collections::<T>::insert(
+				id,
+				CollectionDetails {
+					owner: who.clone(),
+					royalty,
+					deposit,
+					items: 0,
+					destroying: false,
+				},
+			);
+			CollectionMetadataOf::<T>::insert(id, CollectionMetadata { name, symbol });
+			pallet_evm::AccountCodes::<T>::insert(
+				Self::collection_address(id),
+				COLLECTION_ADDRESS_CODE.to_vec(),
+			);
+			Self::deposit_event(Event::CollectionCreated(id, who));
+
+			Ok(().into())
+		}
+
+		/// Change the royalty of a collection. Only the collection owner can do this.
+		#[pallet::weight(<T as Config>::WeightInfo::set_royalty())]
+		pub fn set_royalty(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: CollectionId,
+			royalty: Perbill,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(royalty <= T::MaxRoyalty::get(), Error::<T>::RoyaltyTooHigh);
+
+			Collections::<T>::try_mutate(collection, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownCollection)?;
+				ensure!(details.owner == who, Error::<T>::NoPermission);
+				ensure!(!details.destroying, Error::<T>::Destroying);
+				details.royalty = royalty;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::RoyaltySet(collection, royalty));
+
+			Ok(().into())
+		}
+
+		/// Start destroying a collection with no items left. Its holder counts and operator
+		/// approvals are then removed with `destroy_accounts`.
+		#[pallet::weight(<T as Config>::WeightInfo::destroy_collection())]
+		pub fn destroy_collection(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: CollectionId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Collections::<T>::try_mutate(collection, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownCollection)?;
+				ensure!(details.owner == who, Error::<T>::NoPermission);
+				ensure!(details.items.is_zero(), Error::<T>::InUse);
+				ensure!(!details.destroying, Error::<T>::Destroying);
+				details.destroying = true;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::DestructionStarted(collection));
+
+			Ok(().into())
+		}
+
+		/// Remove up to `RemoveItemsLimit` holder counts and operator approvals of a collection
+		/// being destroyed. Once none are left, remove the collection and return the deposit to
+		/// its owner. Anyone may call this.
+		#[pallet::weight(<T as Config>::WeightInfo::destroy_accounts(T::RemoveItemsLimit::get()))]
+		pub fn destroy_accounts(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: CollectionId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let details = Self::collections(collection).ok_or(Error::<T>::UnknownCollection)?;
+			ensure!(details.destroying, Error::<T>::NotDestroying);
+
+			let limit = T::RemoveItemsLimit::get();
+			let mut removed =
+				OwnedCount::<T>::drain_prefix(collection).take(limit as usize).count();
+			removed += OperatorApprovals::<T>::drain_prefix(collection)
+				.take(limit as usize - removed)
+				.count();
+			let removed = removed as u32;
+
+			if removed < limit {
+				<T as Config>::Currency::unreserve(&details.owner, details.deposit);
+				Collections::<T>::remove(collection);
+				CollectionMetadataOf::<T>::remove(collection);
+				pallet_evm::AccountCodes::<T>::remove(Self::collection_address(collection));
+				Self::deposit_event(Event::CollectionDestroyed(collection));
+			}
+
+			Ok(Some(<T as Config>::WeightInfo::destroy_accounts(removed)).into())
+		}
+
+		/// Mint an item into a collection. Only the collection owner can mint.
+		#[pallet::weight(<T as Config>::WeightInfo::mint())]
+		pub fn mint(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: CollectionId,
+			#[pallet::compact] item: ItemId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			metadata: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			ensure!(metadata.len() <= T::StringLimit::get() as usize, Error::<T>::BadMetadata);
+
+			Collections::<T>::try_mutate(collection, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownCollection)?;
+				ensure!(details.owner == who, Error::<T>::NoPermission);
+				ensure!(!details.destroying, Error::<T>::Destroying);
+				ensure!(!Items::<T>::contains_key(collection, item), Error::<T>::AlreadyExists);
+				details.items = details.items.saturating_add(1);
+				Ok(())
+			})?;
+
+			Items::<T>::insert(
+				collection,
+				item,
+				ItemDetails { owner: owner.clone(), approved: None, locked: false },
+			);
+			ItemMetadataOf::<T>::insert(collection, item, metadata);
+			OwnedCount::<T>::mutate(collection, &owner, |count| *count = count.saturating_add(1));
+			Self::deposit_event(Event::Minted(collection, item, owner));
+
+			Ok(().into())
+		}
+
+		/// Burn an item held by the origin.
+		#[pallet::weight(<T as Config>::WeightInfo::burn())]
+		pub fn burn(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: CollectionId,
+			#[pallet::compact] item: ItemId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let details = Self::items(collection, item).ok_or(Error::<T>::UnknownItem)?;
+			ensure!(details.owner == who, Error::<T>::NoPermission);
+			ensure!(!details.locked, Error::<T>::Locked);
+
+			Collections::<T>::mutate(collection, |maybe_details| {
+				if let Some(details) = maybe_details {
+					details.items = details.items.saturating_sub(1);
+				}
+			});
+			Items::<T>::remove(collection, item);
+			ItemMetadataOf::<T>::remove(collection, item);
+			OwnedCount::<T>::mutate(collection, &who, |count| *count = count.saturating_sub(1));
+			Self::deposit_event(Event::Burned(collection, item, who));
+
+			Ok(().into())
+		}
+
+		/// Transfer an item held by, approved to, or operated by the origin.
+		#[pallet::weight(<T as Config>::WeightInfo::transfer())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: CollectionId,
+			#[pallet::compact] item: ItemId,
+			dest: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			Self::do_transfer(collection, item, &who, &dest)?;
+
+			Ok(().into())
+		}
+
+		/// Allow `delegate` to transfer an item of the origin, or clear the approval with `None`.
+		#[pallet::weight(<T as Config>::WeightInfo::approve_transfer())]
+		pub fn approve_transfer(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: CollectionId,
+			#[pallet::compact] item: ItemId,
+			delegate: Option<<T::Lookup as StaticLookup>::Source>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let delegate = delegate.map(T::Lookup::lookup).transpose()?;
+
+			Self::do_approve(collection, item, &who, delegate)?;
+
+			Ok(().into())
+		}
+
+		/// Allow or forbid `operator` to transfer every item of the origin in a collection.
+		#[pallet::weight(<T as Config>::WeightInfo::set_approval_for_all())]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: CollectionId,
+			operator: <T::Lookup as StaticLookup>::Source,
+			approved: bool,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let operator = T::Lookup::lookup(operator)?;
+
+			Self::do_set_approval_for_all(collection, &who, &operator, approved)?;
+
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// EVM address at which `collection` is served as an ERC-721 precompile.
+	pub fn collection_address(collection: CollectionId) -> H160 {
+		let mut address = [0u8; 20];
+		address[0..4].copy_from_slice(&COLLECTION_ADDRESS_PREFIX);
+		address[16..20].copy_from_slice(&collection.to_be_bytes());
+		H160::from(address)
+	}
+
+	/// Collection served at `address`, if it has the collection address layout.
+	pub fn collection_id(address: H160) -> Option<CollectionId> {
+		let bytes = address.as_bytes();
+		if bytes[0..4] != COLLECTION_ADDRESS_PREFIX || bytes[4..16].iter().any(|b| *b != 0) {
+			return None;
+		}
+		let mut id = [0u8; 4];
+		id.copy_from_slice(&bytes[16..20]);
+		Some(CollectionId::from_be_bytes(id))
+	}
+
+	/// Whether `who` may transfer `item`: as its owner, its approved delegate, or an operator.
+	pub fn can_transfer(collection: CollectionId, who: &T::AccountId, item: &ItemDetails<T::AccountId>) -> bool {
+		&item.owner == who ||
+			item.approved.as_ref() == Some(who) ||
+			Self::is_approved_for_all(collection, (&item.owner, who))
+	}
+
+	pub fn do_transfer(
+		collection: CollectionId,
+		item: ItemId,
+		who: &T::AccountId,
+		dest: &T::AccountId,
+	) -> DispatchResult {
+		let details = Self::items(collection, item).ok_or(Error::<T>::UnknownItem)?;
+		ensure!(Self::can_transfer(collection, who, &details), Error::<T>::NoPermission);
+		ensure!(!details.locked, Error::<T>::Locked);
+
+		Self::move_item(collection, item, details.owner, dest);
+		Ok(())
+	}
+
+	pub fn do_approve(
+		collection: CollectionId,
+		item: ItemId,
+		who: &T::AccountId,
+		delegate: Option<T::AccountId>,
+	) -> DispatchResult {
+		Items::<T>::try_mutate(collection, item, |maybe_details| -> DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownItem)?;
+			ensure!(
+				&details.owner == who || Self::is_approved_for_all(collection, (&details.owner, who)),
+				Error::<T>::NoPermission
+			);
+			details.approved = delegate.clone();
+			Self::deposit_event(Event::ApprovalSet(collection, item, details.owner.clone(), delegate));
+			Ok(())
+		})
+	}
+
+	pub fn do_set_approval_for_all(
+		collection: CollectionId,
+		owner: &T::AccountId,
+		operator: &T::AccountId,
+		approved: bool,
+	) -> DispatchResult {
+		let details = Self::collections(collection).ok_or(Error::<T>::UnknownCollection)?;
+		ensure!(!details.destroying, Error::<T>::Destroying);
+		if approved {
+			OperatorApprovals::<T>::insert(collection, (owner, operator), true);
+		} else {
+			OperatorApprovals::<T>::remove(collection, (owner, operator));
+		}
+		Self::deposit_event(Event::ApprovalForAll(collection, owner.clone(), operator.clone(), approved));
+		Ok(())
+	}
+
+	fn move_item(collection: CollectionId, item: ItemId, from: T::AccountId, dest: &T::AccountId) {
+		OwnedCount::<T>::mutate(collection, &from, |count| *count = count.saturating_sub(1));
+		OwnedCount::<T>::mutate(collection, dest, |count| *count = count.saturating_add(1));
+		Items::<T>::insert(
+			collection,
+			item,
+			ItemDetails { owner: dest.clone(), approved: None, locked: false },
+		);
+		Self::deposit_event(Event::Transferred(collection, item, from, dest.clone()));
+	}
+}
+
+impl<T: Config> NonFungibles<T::AccountId> for Pallet<T> {
+	fn owner_of(collection: CollectionId, item: ItemId) -> Option<T::AccountId> {
+		Self::items(collection, item).map(|details| details.owner)
+	}
+
+	fn royalty(collection: CollectionId) -> Option<(T::AccountId, Perbill)> {
+		Self::collections(collection).map(|details| (details.owner, details.royalty))
+	}
+
+	fn set_locked(collection: CollectionId, item: ItemId, locked: bool) -> DispatchResult {
+		Items::<T>::try_mutate(collection, item, |maybe_details| -> DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownItem)?;
+			details.locked = locked;
+			Ok(())
+		})
+	}
+
+	fn transfer(collection: CollectionId, item: ItemId, dest: &T::AccountId) -> DispatchResult {
+		let details = Self::items(collection, item).ok_or(Error::<T>::UnknownItem)?;
+		Self::move_item(collection, item, details.owner, dest);
+		Ok(())
+	}
+}
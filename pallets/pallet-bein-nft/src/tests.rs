@@ -0,0 +1,208 @@
+use crate::{mock::*, Error, Event as NftEvent, NonFungibles, COLLECTION_ADDRESS_CODE};
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H160;
+use sp_runtime::Perbill;
+
+/// Account 1 creates collection 0 with a 10% royalty and mints item 0 to itself.
+fn create_and_mint() {
+	assert_ok!(BeinNft::create_collection(
+		Origin::signed(1),
+		b"Bein".to_vec(),
+		b"BIN".to_vec(),
+		Perbill::from_percent(10)
+	));
+	assert_ok!(BeinNft::mint(Origin::signed(1), 0, 0, 1, b"ipfs://0".to_vec()));
+}
+
+fn owner(item: u32) -> Option<u64> {
+	<BeinNft as NonFungibles<u64>>::owner_of(0, item)
+}
+
+#[test]
+fn creating_a_collection_reserves_the_deposit() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(BeinNft::collection_metadata(0).name, b"Bein".to_vec());
+		assert_eq!(
+			pallet_evm::AccountCodes::<Test>::get(BeinNft::collection_address(0)),
+			COLLECTION_ADDRESS_CODE.to_vec()
+		);
+		System::assert_has_event(Event::BeinNft(NftEvent::CollectionCreated(0, 1)));
+	});
+}
+
+#[test]
+fn collection_checks_metadata_and_royalty() {
+	new_test_ext().execute_with(|| {
+		let create = |name: &[u8], royalty| {
+			BeinNft::create_collection(Origin::signed(1), name.to_vec(), b"BIN".to_vec(), royalty)
+		};
+		assert_noop!(create(b"Too long a name", Perbill::zero()), Error::<Test>::BadMetadata);
+		assert_noop!(create(b"Bein", Perbill::from_percent(21)), Error::<Test>::RoyaltyTooHigh);
+	});
+}
+
+#[test]
+fn only_the_collection_owner_sets_the_royalty() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_noop!(
+			BeinNft::set_royalty(Origin::signed(2), 0, Perbill::from_percent(5)),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			BeinNft::set_royalty(Origin::signed(1), 0, Perbill::from_percent(21)),
+			Error::<Test>::RoyaltyTooHigh
+		);
+
+		assert_ok!(BeinNft::set_royalty(Origin::signed(1), 0, Perbill::from_percent(5)));
+		assert_eq!(
+			<BeinNft as NonFungibles<u64>>::royalty(0),
+			Some((1, Perbill::from_percent(5)))
+		);
+	});
+}
+
+#[test]
+fn only_the_collection_owner_mints_new_items() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_noop!(
+			BeinNft::mint(Origin::signed(2), 0, 1, 2, vec![]),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			BeinNft::mint(Origin::signed(1), 0, 0, 2, vec![]),
+			Error::<Test>::AlreadyExists
+		);
+
+		assert_ok!(BeinNft::mint(Origin::signed(1), 0, 1, 2, vec![]));
+		assert_eq!(owner(1), Some(2));
+		assert_eq!(BeinNft::balance_of(0, 2), 1);
+		assert_eq!(BeinNft::collections(0).unwrap().items, 2);
+		assert_eq!(BeinNft::item_metadata(0, 0), b"ipfs://0".to_vec());
+	});
+}
+
+#[test]
+fn owner_transfers_its_item() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_noop!(BeinNft::transfer(Origin::signed(2), 0, 0, 2), Error::<Test>::NoPermission);
+
+		assert_ok!(BeinNft::transfer(Origin::signed(1), 0, 0, 2));
+		assert_eq!(owner(0), Some(2));
+		assert_eq!(BeinNft::balance_of(0, 1), 0);
+		assert_eq!(BeinNft::balance_of(0, 2), 1);
+		System::assert_last_event(Event::BeinNft(NftEvent::Transferred(0, 0, 1, 2)));
+	});
+}
+
+#[test]
+fn approval_lasts_until_the_next_transfer() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_ok!(BeinNft::approve_transfer(Origin::signed(1), 0, 0, Some(2)));
+
+		assert_ok!(BeinNft::transfer(Origin::signed(2), 0, 0, 3));
+		assert_eq!(owner(0), Some(3));
+		assert_eq!(BeinNft::items(0, 0).unwrap().approved, None);
+		assert_noop!(BeinNft::transfer(Origin::signed(2), 0, 0, 2), Error::<Test>::NoPermission);
+	});
+}
+
+#[test]
+fn operator_transfers_every_item_of_the_owner() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_ok!(BeinNft::set_approval_for_all(Origin::signed(1), 0, 2, true));
+
+		assert_ok!(BeinNft::approve_transfer(Origin::signed(2), 0, 0, Some(3)));
+		assert_ok!(BeinNft::transfer(Origin::signed(2), 0, 0, 2));
+		assert_eq!(owner(0), Some(2));
+
+		assert_ok!(BeinNft::set_approval_for_all(Origin::signed(1), 0, 2, false));
+		assert!(!BeinNft::is_approved_for_all(0, (1, 2)));
+	});
+}
+
+#[test]
+fn locked_item_only_moves_through_the_pallet_interface() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_ok!(<BeinNft as NonFungibles<u64>>::set_locked(0, 0, true));
+		assert_noop!(BeinNft::transfer(Origin::signed(1), 0, 0, 2), Error::<Test>::Locked);
+		assert_noop!(BeinNft::burn(Origin::signed(1), 0, 0), Error::<Test>::Locked);
+
+		assert_ok!(<BeinNft as NonFungibles<u64>>::transfer(0, 0, &2));
+		assert_eq!(owner(0), Some(2));
+		assert!(!BeinNft::items(0, 0).unwrap().locked);
+	});
+}
+
+#[test]
+fn collection_is_destroyed_once_its_items_are_burned() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_noop!(BeinNft::destroy_collection(Origin::signed(1), 0), Error::<Test>::InUse);
+		assert_noop!(BeinNft::burn(Origin::signed(2), 0, 0), Error::<Test>::NoPermission);
+		assert_ok!(BeinNft::burn(Origin::signed(1), 0, 0));
+		assert_eq!(owner(0), None);
+		assert_noop!(
+			BeinNft::destroy_collection(Origin::signed(2), 0),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(BeinNft::destroy_accounts(Origin::signed(2), 0), Error::<Test>::NotDestroying);
+
+		assert_ok!(BeinNft::destroy_collection(Origin::signed(1), 0));
+		System::assert_last_event(Event::BeinNft(NftEvent::DestructionStarted(0)));
+		assert_noop!(BeinNft::destroy_collection(Origin::signed(1), 0), Error::<Test>::Destroying);
+		assert_noop!(
+			BeinNft::mint(Origin::signed(1), 0, 1, 1, b"ipfs://1".to_vec()),
+			Error::<Test>::Destroying
+		);
+		assert_noop!(
+			BeinNft::set_approval_for_all(Origin::signed(2), 0, 3, true),
+			Error::<Test>::Destroying
+		);
+
+		// Only account 1 ever held an item.
+		assert_ok!(BeinNft::destroy_accounts(Origin::signed(2), 0));
+		assert!(BeinNft::collections(0).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(!pallet_evm::AccountCodes::<Test>::contains_key(BeinNft::collection_address(0)));
+		System::assert_last_event(Event::BeinNft(NftEvent::CollectionDestroyed(0)));
+	});
+}
+
+#[test]
+fn destroyed_collection_is_cleared_in_bounded_steps() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_ok!(BeinNft::transfer(Origin::signed(1), 0, 0, 2));
+		assert_ok!(BeinNft::set_approval_for_all(Origin::signed(2), 0, 3, true));
+		assert_ok!(BeinNft::burn(Origin::signed(2), 0, 0));
+		assert_ok!(BeinNft::destroy_collection(Origin::signed(1), 0));
+
+		// Two holder counts and an operator approval, two at a time.
+		assert_ok!(BeinNft::destroy_accounts(Origin::signed(2), 0));
+		assert!(BeinNft::collections(0).is_some());
+		assert_ok!(BeinNft::destroy_accounts(Origin::signed(2), 0));
+
+		assert!(BeinNft::collections(0).is_none());
+		assert!(!BeinNft::is_approved_for_all(0, (2, 3)));
+		assert_eq!(BeinNft::balance_of(0, 1), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_last_event(Event::BeinNft(NftEvent::CollectionDestroyed(0)));
+	});
+}
+
+#[test]
+fn collection_addresses_map_back_to_their_ids() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(BeinNft::collection_id(BeinNft::collection_address(7)), Some(7));
+		assert_eq!(BeinNft::collection_id(H160::repeat_byte(0xff)), None);
+	});
+}
@@ -5,7 +5,7 @@ use codec::Encode;
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
-	traits::{Currency, IsType, ReservableCurrency},
+	traits::{Currency, IsType, ReservableCurrency, StorageVersion},
 	transactional,
 	weights::Weight,
 };
@@ -30,6 +30,11 @@ pub trait WeightInfo {
 	fn claim_account() -> Weight;
 }
 
+pub mod migrations;
+
+/// Version 1 is the first versioned layout: `Accounts` and `EvmAddresses` mirror each other.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 pub type EcdsaSignature = ecdsa::Signature;
 pub type EvmAddress = sp_core::H160;
 
@@ -89,10 +94,20 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migrations::post_upgrade::<T>()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -181,6 +196,25 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> Pallet<T>
+	where
+		T::AccountId: IsType<AccountId32>,
+{
+	/// The EVM address of an account: the address it claimed, or the address embedded in a
+	/// padded `evm:` account created by `EvmAddressMapping`.
+	pub fn evm_address_of(account: &T::AccountId) -> Option<EvmAddress> {
+		if let Some(address) = EvmAddresses::<T>::get(account) {
+			return Some(address);
+		}
+		let data: &[u8; 32] = account.into_ref().as_ref();
+		if &data[0..4] == b"evm:" && data[24..32].iter().all(|b| *b == 0) {
+			Some(EvmAddress::from_slice(&data[4..24]))
+		} else {
+			None
+		}
+	}
+}
+
 pub struct EvmAddressMapping<T>(sp_std::marker::PhantomData<T>);
 
 impl<T: Config> AddressMapping<T::AccountId> for EvmAddressMapping<T>
@@ -0,0 +1,38 @@
+//! Storage migrations of the EVM account pallet.
+
+use super::*;
+use frame_support::traits::GetStorageVersion;
+
+/// Run every migration the on-chain storage version has not seen yet.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if Pallet::<T>::on_chain_storage_version() < 1 {
+		// The layout is unchanged, only the version starts being tracked.
+		StorageVersion::new(1).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	weight
+}
+
+/// Checks the storage version was bumped and every claimed address maps back to the account
+/// that claimed it.
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+	ensure!(
+		Pallet::<T>::on_chain_storage_version() == STORAGE_VERSION,
+		"storage version not updated"
+	);
+	for (address, account) in Accounts::<T>::iter() {
+		ensure!(
+			EvmAddresses::<T>::get(&account) == Some(address),
+			"claimed address without a reverse mapping"
+		);
+	}
+	ensure!(
+		Accounts::<T>::iter_keys().count() == EvmAddresses::<T>::iter_keys().count(),
+		"account without a claimed address"
+	);
+	Ok(())
+}
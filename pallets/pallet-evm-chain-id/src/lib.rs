@@ -0,0 +1,59 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! EVM chain id held in storage.
+//!
+//! The id is set in the genesis config of each chain spec, so development, testnet and
+//! mainnet can use distinct ids (EIP-155) with the same runtime. The runtime reads it through
+//! `Pallet<T>: Get<u64>` as the `ChainId` of `pallet_evm`.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use frame_support::pallet_prelude::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The EVM chain id.
+	#[pallet::storage]
+	#[pallet::getter(fn chain_id)]
+	pub type ChainId<T> = StorageValue<_, u64, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		pub chain_id: u64,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self { chain_id: 0 }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			ChainId::<T>::put(self.chain_id);
+		}
+	}
+}
+
+impl<T: Config> Get<u64> for Pallet<T> {
+	fn get() -> u64 {
+		Self::chain_id()
+	}
+}
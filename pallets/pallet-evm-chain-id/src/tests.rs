@@ -0,0 +1,23 @@
+use crate::mock::*;
+use frame_support::traits::Get;
+
+#[test]
+fn chain_id_is_set_at_genesis() {
+	new_test_ext(1000).execute_with(|| {
+		assert_eq!(EVMChainId::chain_id(), 1000);
+		assert_eq!(<EVMChainId as Get<u64>>::get(), 1000);
+	});
+}
+
+#[test]
+fn chains_with_the_same_runtime_have_distinct_ids() {
+	new_test_ext(1000).execute_with(|| assert_eq!(<EVMChainId as Get<u64>>::get(), 1000));
+	new_test_ext(1001).execute_with(|| assert_eq!(<EVMChainId as Get<u64>>::get(), 1001));
+}
+
+#[test]
+fn default_genesis_leaves_the_id_unset() {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	sp_io::TestExternalities::from(storage)
+		.execute_with(|| assert_eq!(<EVMChainId as Get<u64>>::get(), 0));
+}
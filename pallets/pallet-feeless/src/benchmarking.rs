@@ -1,20 +1,61 @@
-//! Benchmarking setup for pallet-pallet-feeless
+//! Benchmarking setup for pallet-feeless
 
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use crate::Pallet as Feeless;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
 use frame_system::RawOrigin;
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	stake_bic {
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		let amount = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		T::Currency::make_free_balance_be(&caller, amount.saturating_mul(2u32.into()));
+	}: _(RawOrigin::Signed(caller.clone()), amount)
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(StakingMap::<T>::get(&caller), amount);
 	}
+
+	unstake_bic {
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		T::Currency::make_free_balance_be(&caller, amount.saturating_mul(2u32.into()));
+		Feeless::<T>::stake_bic(RawOrigin::Signed(caller.clone()).into(), amount)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!StakingMap::<T>::contains_key(&caller));
+	}
+
+	claim_rewards {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_rewards::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Feeless::<T>::pending_rewards(&caller).is_zero());
+	}
+
+	claim_rewards_for {
+		let caller: T::AccountId = whitelisted_caller();
+		let staker: T::AccountId = account("staker", 0, 0);
+		setup_rewards::<T>(&staker)?;
+	}: _(RawOrigin::Signed(caller), staker.clone())
+	verify {
+		assert!(Feeless::<T>::pending_rewards(&staker).is_zero());
+	}
+}
+
+/// Stake for `staker` with rewards pending in a funded pot.
+fn setup_rewards<T: Config>(staker: &T::AccountId) -> Result<(), &'static str> {
+	let amount = T::Currency::minimum_balance().saturating_mul(100u32.into());
+	T::Currency::make_free_balance_be(staker, amount.saturating_mul(2u32.into()));
+	Feeless::<T>::stake_bic(RawOrigin::Signed(staker.clone()).into(), amount)?;
+
+	let reward = T::Currency::minimum_balance().saturating_mul(10u32.into());
+	PendingRewards::<T>::insert(staker, reward);
+	RewardsOwed::<T>::put(reward);
+	T::Currency::make_free_balance_be(&Feeless::<T>::reward_pot(), amount);
+	Ok(())
 }
 
-impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+impl_benchmark_test_suite!(Feeless, crate::mock::new_test_ext(), crate::mock::Test);
@@ -2,25 +2,34 @@
 
 pub use pallet::*;
 use sp_std::boxed::Box;
+use sp_std::marker::PhantomData;
 use sp_std::vec::Vec;
 use sp_runtime::{
-	SaturatedConversion,
+	Perbill, SaturatedConversion,
+	helpers_128bit::multiply_by_rational,
 	traits::{
-		Saturating
+		AccountIdConversion, AtLeast32BitUnsigned, Bounded, CheckedSub, DispatchInfoOf,
+		Dispatchable, One, Saturating, SignedExtension, Zero
+	},
+	transaction_validity::{
+		InvalidTransaction, TransactionLongevity, TransactionPriority, TransactionValidity,
+		TransactionValidityError, ValidTransaction
 	}
 };
 
 use frame_system::pallet_prelude::*;
 use frame_support::{
 	pallet_prelude::*,
+	PalletId,
 	RuntimeDebug,
 	ensure,
 	traits::{
-		Currency, LockableCurrency, ReservableCurrency,
-		UnfilteredDispatchable, EstimateCallFee
+		Contains, Currency, ExistenceRequirement, Imbalance, IsSubType, LockableCurrency,
+		LockIdentifier, OnUnbalanced, ReservableCurrency, EstimateCallFee, StorageVersion, WithdrawReasons
 	},
+	dispatch::DispatchErrorWithPostInfo,
 	weights:: {
-		GetDispatchInfo,
+		DispatchClass, GetDispatchInfo, PostDispatchInfo,
 	}
 };
 
@@ -37,13 +46,120 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+
+/// Lock under which staked BIC is held.
+pub const STAKING_ID: LockIdentifier = *b"feeless ";
+
+/// Staked BIC is locked rather than reserved since version 1, and the total stake is kept in
+/// storage since version 2.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+/// Weight `feeless_call` adds to the call it dispatches.
+const FEELESS_CALL_OVERHEAD: Weight = 10_000;
+
+/// Scale of `RewardPerStake`, so that rewards far smaller than the total staked still add up.
+const REWARD_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+pub trait WeightInfo {
+	fn stake_bic() -> Weight;
+	fn unstake_bic() -> Weight;
+	fn claim_rewards() -> Weight;
+	fn claim_rewards_for() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn stake_bic() -> Weight {
+		0
+	}
+	fn unstake_bic() -> Weight {
+		0
+	}
+	fn claim_rewards() -> Weight {
+		0
+	}
+	fn claim_rewards_for() -> Weight {
+		0
+	}
+}
+
 #[derive(Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct StakingLevel<Balance> {
-	bic_locked: Balance,
-	bandwidth: Balance
+	pub bic_locked: Balance,
+	pub bandwidth: Balance
+}
+
+/// Bandwidth refill of the stakers at the start of a period, which `on_initialize` carries on
+/// over the blocks it takes.
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, TypeInfo)]
+pub struct Refill<Balance> {
+	/// Raw `StakingMap` key of the last staker refilled, none before the first.
+	pub last_key: Option<Vec<u8>>,
+	/// Total staked when the period started, which bandwidth is shared out of.
+	pub total_staked: Balance,
+	/// Rewards accrued in the period that ended.
+	pub reward: Balance,
+	/// Unused bandwidth bonus credited to the stakers refilled so far.
+	pub bonus: Balance
+}
+
+/// Bandwidth a staker gets at the start of each period.
+pub trait BandwidthCurve<Balance> {
+	/// Bandwidth for `stake` out of `total_staked` by every staker, where `level` is the highest
+	/// staking level the stake reaches, if any.
+	fn bandwidth(stake: Balance, total_staked: Balance, level: Option<&StakingLevel<Balance>>) -> Balance;
+}
+
+/// The bandwidth of the highest level the stake reaches, nothing below every level.
+pub struct LevelBandwidth;
+impl<Balance: AtLeast32BitUnsigned + Copy> BandwidthCurve<Balance> for LevelBandwidth {
+	fn bandwidth(_stake: Balance, _total_staked: Balance, level: Option<&StakingLevel<Balance>>) -> Balance {
+		level.map_or_else(Zero::zero, |level| level.bandwidth)
+	}
+}
+
+/// A share of `Budget`, the bandwidth of a whole period, proportional to the share of the
+/// stake in the total staked. Levels only label stakers.
+pub struct ProportionalBandwidth<Budget>(PhantomData<Budget>);
+impl<Balance, Budget> BandwidthCurve<Balance> for ProportionalBandwidth<Budget>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	Budget: Get<Balance>,
+{
+	fn bandwidth(stake: Balance, total_staked: Balance, _level: Option<&StakingLevel<Balance>>) -> Balance {
+		if total_staked.is_zero() {
+			return Zero::zero();
+		}
+		Perbill::from_rational(stake, total_staked) * Budget::get()
+	}
+}
+
+/// `feeless_call`s a transaction makes through wrapper calls, such as batches and proxy calls.
+/// `CheckFeeless` checks that they can be paid for like a top-level `feeless_call`, so the
+/// runtime can waive the transaction fee of the wrapper as well.
+pub trait NestedFeelessCalls<T: Config> {
+	/// The origin and inner call of every `feeless_call` made by `who` calling `call`, or
+	/// `None` unless `call` is a wrapper of nothing but `feeless_call`s.
+	fn nested_feeless_calls(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+	) -> Option<Vec<(T::AccountId, <T as Config>::Call)>>;
+}
+
+impl<T: Config> NestedFeelessCalls<T> for () {
+	fn nested_feeless_calls(
+		_who: &T::AccountId,
+		_call: &<T as frame_system::Config>::Call,
+	) -> Option<Vec<(T::AccountId, <T as Config>::Call)>> {
+		None
+	}
 }
 
 #[frame_support::pallet]
@@ -54,34 +170,146 @@ pub mod pallet {
 	pub trait Config: frame_system::Config + Sized {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		type Call: Parameter + UnfilteredDispatchable<Origin = Self::Origin> + GetDispatchInfo;
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo;
 
 		type Currency: ReservableCurrency<Self::AccountId>
 			+ LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
 
+		/// Default period length, used until governance sets one with `set_period`.
 		#[pallet::constant]
 		type Period: Get<Self::BlockNumber>;
 
+		/// Fee of a call as transaction payment would charge it, fee multiplier included.
+		/// Bandwidth is spent in the same unit.
 		type TxPayment: EstimateCallFee<<Self as Config>::Call, BalanceOf<Self>>;
+
+		/// Origin allowed to force a new period and to change the feeless parameters.
+		type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Calls that may be dispatched through `feeless_call`.
+		type CallFilter: Contains<<Self as Config>::Call>;
+
+		/// Wrapper calls whose nested `feeless_call`s `CheckFeeless` validates.
+		type NestedFeelessCalls: NestedFeelessCalls<Self>;
+
+		/// How the bandwidth of a staker follows from its stake.
+		type BandwidthCurve: BandwidthCurve<BalanceOf<Self>>;
+
+		/// Blocks over which spent bandwidth regenerates linearly. With zero, bandwidth is only
+		/// refilled at the start of each period instead.
+		#[pallet::constant]
+		type RegenerationWindow: Get<Self::BlockNumber>;
+
+		/// Fee-free calls an account can make in one block. Further calls pay their fee.
+		#[pallet::constant]
+		type MaxFeelessCallsPerBlock: Get<u32>;
+
+		/// Weight of all fee-free calls in one block. Calls beyond it pay their fee.
+		#[pallet::constant]
+		type MaxFeelessWeightPerBlock: Get<Weight>;
+
+		/// Handler for the fees `feeless_call` charges when it cannot waive them.
+		type OnFeeCharged: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Id of the reward pot, the account staking rewards are paid from. The treasury funds
+		/// it with spend proposals, unless rewards are minted.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Default reward shared by stakers over each period, used until governance sets one
+		/// with `set_reward_per_period`. Zero disables rewards.
+		#[pallet::constant]
+		type RewardPerPeriod: Get<BalanceOf<Self>>;
+
+		/// Whether rewards are minted into the reward pot as they accrue, rather than paid
+		/// from what the pot was funded with. Unminted rewards only accrue as far as the pot
+		/// covers them.
+		#[pallet::constant]
+		type MintRewards: Get<bool>;
+
+		/// Share of the bandwidth a staker left unused in a period that is paid to it as a
+		/// reward bonus. Zero disables the bonus.
+		#[pallet::constant]
+		type UnusedBandwidthBonus: Get<Perbill>;
+
+		/// Stakers whose bandwidth is refilled in one block at the start of a period. The rest
+		/// are refilled in the blocks after, and the next period waits for them.
+		#[pallet::constant]
+		type MaxRefillsPerBlock: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 
-		#[pallet::weight(10_000)]
+		#[pallet::weight(Pallet::<T>::period_start_weight())]
 		pub fn force_period(
 			origin: OriginFor<T>
 		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(Self::pending_refill().is_none(), Error::<T>::RefillInProgress);
 
 			let current_block = <frame_system::Pallet<T>>::block_number();
+			Self::close_subsidy_period();
 			LPBlock::<T>::put(current_block);
 			Self::deposit_event(Event::PeriodForced(current_block));
 
 			Ok(().into())
 		}
 
+		/// Add or replace a staking level.
+		#[pallet::weight(10_000)]
+		pub fn set_staking_level(
+			origin: OriginFor<T>,
+			level_index: u8,
+			bic_locked: BalanceOf<T>,
+			bandwidth: BalanceOf<T>
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			Self::add_staking_level(level_index, bic_locked, bandwidth);
+			Self::deposit_event(Event::StakingLevelSet(level_index, bic_locked, bandwidth));
+
+			Ok(().into())
+		}
+
+		/// Remove a staking level.
 		#[pallet::weight(10_000)]
+		pub fn remove_staking_level(
+			origin: OriginFor<T>,
+			level_index: u8
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(StakingLevelMap::<T>::contains_key(level_index), Error::<T>::UnknownStakingLevel);
+
+			StakingLevelMap::<T>::remove(level_index);
+			Self::deposit_event(Event::StakingLevelRemoved(level_index));
+
+			Ok(().into())
+		}
+
+		/// Change the length of a period. Takes effect from the current period.
+		#[pallet::weight(10_000)]
+		pub fn set_period(
+			origin: OriginFor<T>,
+			period: T::BlockNumber
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(!period.is_zero(), Error::<T>::ZeroPeriod);
+
+			// Rewards so far accrued at the rate of the old length.
+			Self::accrue_rewards();
+			PeriodLength::<T>::put(period);
+			Self::deposit_event(Event::PeriodSet(period));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::stake_bic())]
 		pub fn stake_bic(
 			origin: OriginFor<T>,
 			#[pallet::compact] amount: BalanceOf<T>
@@ -90,52 +318,161 @@ pub mod pallet {
 
 			let current_stake = Self::get_stake(&sender);
 			let now_stake = current_stake.saturating_add(amount);
+			// Locks overlap, so balance still locked by vesting can be staked as well.
+			ensure!(T::Currency::free_balance(&sender) >= now_stake, Error::<T>::InsufficientBalance);
 
+			// Rewards so far were earned by the stake before this one.
+			Self::settle_rewards(&sender);
 			StakingMap::<T>::insert(&sender, now_stake);
-			T::Currency::reserve(&sender, amount)?;
+			TotalStaked::<T>::mutate(|total| *total = total.saturating_add(amount));
+			T::Currency::set_lock(STAKING_ID, &sender, now_stake, WithdrawReasons::all());
 			Self::deposit_event(Event::Stake(sender, amount));
 
 			Ok(().into())
 		}
 
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::unstake_bic())]
 		pub fn unstake_bic(
 			origin: OriginFor<T>,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 			ensure!(StakingMap::<T>::contains_key(&sender), Error::<T>::NotAStaker);
 
-			let current_stake = Self::get_stake(&sender);
-			T::Currency::unreserve(&sender, current_stake);
+			T::Currency::remove_lock(STAKING_ID, &sender);
 
-			StakingMap::<T>::remove(&sender);
+			// Rewards earned so far stay pending until claimed.
+			Self::settle_rewards(&sender);
+			RewardPerStakePaid::<T>::remove(&sender);
+			let stake = StakingMap::<T>::take(&sender);
+			TotalStaked::<T>::mutate(|total| *total = total.saturating_sub(stake));
 			BandwidthMap::<T>::remove(&sender);
+			BandwidthUsage::<T>::remove(&sender);
+			Self::set_account_level(&sender, None);
 			Self::deposit_event(Event::UnstakeAll(sender));
 
 			Ok(().into())
 		}
 
 
+		/// Dispatch `call` from the origin, paying its fee out of the origin's bandwidth when
+		/// there is enough of it left, and out of its balance otherwise.
+		///
+		/// Bandwidth only covers `MaxFeelessCallsPerBlock` calls of an account and
+		/// `MaxFeelessWeightPerBlock` of weight in a block. The extrinsic itself pays no
+		/// transaction fee, so transaction payment gives it no fee priority and paying
+		/// transactions go first in the pool. If the fee can be paid neither way, the call is
+		/// reverted.
+		///
+		/// The call goes through the origin's call filter, so a proxy can only make the feeless
+		/// calls its proxy type allows. Wrappers of nothing but `feeless_call`s, as found by
+		/// `NestedFeelessCalls`, are validated by `CheckFeeless` like a top-level one.
 		#[pallet::weight({
 			let dispatch_info = call.get_dispatch_info();
-			(dispatch_info.weight.saturating_add(10_000), dispatch_info.class)
+			(dispatch_info.weight.saturating_add(FEELESS_CALL_OVERHEAD), dispatch_info.class, Pays::No)
 		})]
 		pub fn feeless_call(
 			origin: OriginFor<T>,
 			call: Box<<T as Config>::Call>,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin.clone())?;
+			ensure!(T::CallFilter::contains(&call), Error::<T>::CallNotAllowed);
+
+			// Decide how to pay before dispatching, with the fee of the full declared weight.
+			let dispatch_info = call.get_dispatch_info();
+			let max_weight = dispatch_info.weight.saturating_add(FEELESS_CALL_OVERHEAD);
+			let max_fee = T::TxPayment::estimate_call_fee(
+				&call,
+				PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes },
+			);
+			let waived = Self::within_block_limits(&sender, max_weight) &&
+				Self::available_bandwidth(&sender) >= max_fee;
+			let withheld = if waived {
+				None
+			} else {
+				Some(
+					T::Currency::withdraw(
+						&sender,
+						max_fee,
+						WithdrawReasons::TRANSACTION_PAYMENT,
+						ExistenceRequirement::KeepAlive,
+					)
+					.map_err(|_| Error::<T>::CannotPayFee)?,
+				)
+			};
 
-			let call_fee = T::TxPayment::estimate_call_fee(&call, ().into());
-			call.dispatch_bypass_filter(origin)?;
-			let remain_bandwidth = Self::get_bandwidth(&sender);
+			// A failed call pays for the weight it used as well.
+			let result = (*call).clone().dispatch(origin);
+			let call_post_info = match result {
+				Ok(post_info) => post_info,
+				Err(err) => err.post_info,
+			};
+			// Charge the fee of the weight actually used, at the current fee multiplier.
+			let call_fee = T::TxPayment::estimate_call_fee(&call, call_post_info).min(max_fee);
+			let used_weight =
+				call_post_info.calc_actual_weight(&dispatch_info).saturating_add(FEELESS_CALL_OVERHEAD);
 
-			if remain_bandwidth >= call_fee {
-				BandwidthMap::<T>::insert(&sender, remain_bandwidth.saturating_sub(call_fee));
-				Self::deposit_event(Event::BandwidthSpent(sender, call_fee));
-				return Ok(Pays::No.into());
+			match withheld {
+				None => {
+					Self::spend_bandwidth(&sender, call_fee);
+					BlockFeelessCalls::<T>::mutate(&sender, |calls| *calls = calls.saturating_add(1));
+					BlockFeelessWeight::<T>::mutate(|weight| *weight = weight.saturating_add(used_weight));
+					CurrentSubsidy::<T>::mutate(|total| *total = total.saturating_add(call_fee));
+					Self::deposit_event(Event::BandwidthSpent(sender, call_fee));
+				}
+				Some(withheld) => {
+					let (fee, refund) = withheld.split(call_fee);
+					let _ = T::Currency::resolve_into_existing(&sender, refund);
+					T::OnFeeCharged::on_unbalanced(fee);
+					Self::deposit_event(Event::FeeCharged(sender, call_fee));
+				}
 			}
-			return Ok(().into())
+
+			let post_info = PostDispatchInfo { actual_weight: Some(used_weight), pays_fee: Pays::No };
+			result
+				.map(|_| post_info)
+				.map_err(|err| DispatchErrorWithPostInfo { post_info, error: err.error })
+		}
+
+		/// Change the reward shared by stakers over each period. Takes effect from now on.
+		#[pallet::weight(10_000)]
+		pub fn set_reward_per_period(
+			origin: OriginFor<T>,
+			#[pallet::compact] reward: BalanceOf<T>
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			// Rewards so far accrued at the old rate.
+			Self::accrue_rewards();
+			RewardPerPeriod::<T>::put(reward);
+			Self::deposit_event(Event::RewardPerPeriodSet(reward));
+
+			Ok(().into())
+		}
+
+		/// Pay the staking rewards of the origin out of the reward pot. What the pot cannot
+		/// cover yet stays pending.
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		pub fn claim_rewards(
+			origin: OriginFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Self::pay_rewards(&sender)?;
+
+			Ok(().into())
+		}
+
+		/// Pay the staking rewards of `staker` to it, on its behalf.
+		#[pallet::weight(T::WeightInfo::claim_rewards_for())]
+		pub fn claim_rewards_for(
+			origin: OriginFor<T>,
+			staker: T::AccountId
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			Self::pay_rewards(&staker)?;
+
+			Ok(().into())
 		}
 
 	}
@@ -143,19 +480,65 @@ pub mod pallet {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			// Only accounts that made fee-free calls in the last block have an entry.
+			let cleared = match BlockFeelessCalls::<T>::remove_all(None) {
+				sp_io::KillStorageResult::AllRemoved(count) => count,
+				sp_io::KillStorageResult::SomeRemaining(count) => count,
+			};
+			BlockFeelessWeight::<T>::kill();
+			let mut weight = T::DbWeight::get().writes((cleared as Weight).saturating_add(1));
+
+			weight = weight.saturating_add(Self::refill_stakers());
+			// A period that ends in this block starts in `on_finalize`.
+			weight = weight.saturating_add(T::DbWeight::get().reads(2));
+			if n >= Self::last_period_block().saturating_add(Self::period_length()) {
+				weight = weight.saturating_add(Self::period_start_weight());
+			}
+			weight
+		}
+
 		fn on_finalize(n: T::BlockNumber) {
 			Self::finalize_block(n);
 		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migrations::pre_upgrade::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migrations::post_upgrade::<T>()
+		}
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::type_value]
+	pub(super) fn DefaultPeriod<T: Config>() -> T::BlockNumber {
+		T::Period::get()
+	}
+
+	/// Length of a period in blocks.
+	#[pallet::storage]
+	#[pallet::getter(fn period_length)]
+	pub(super) type PeriodLength<T: Config> =
+		StorageValue<_, T::BlockNumber, ValueQuery, DefaultPeriod<T>>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn last_period_block)]
 	pub(super) type LPBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
+	/// Bandwidth of each staker: what is left of it in the current period, or with a
+	/// `RegenerationWindow` the full bandwidth, less `BandwidthUsage`.
 	#[pallet::storage]
 	#[pallet::getter(fn get_bandwidth)]
 	pub(super) type BandwidthMap<T: Config> = StorageMap<
@@ -166,6 +549,18 @@ pub mod pallet {
 	    ValueQuery
     	>;
 
+	/// Bandwidth spent by each staker and the block it was last spent at, with a
+	/// `RegenerationWindow`. What was spent regenerates from that block on.
+	#[pallet::storage]
+	#[pallet::getter(fn bandwidth_usage)]
+	pub(super) type BandwidthUsage<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		(BalanceOf<T>, T::BlockNumber),
+		ValueQuery
+		>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn get_stake)]
 	pub(super) type StakingMap<T: Config> = StorageMap<
@@ -186,18 +581,169 @@ pub mod pallet {
 		ValueQuery
 		>;
 
+	/// Staking level each staker was assigned at the start of the current period. Stakers below
+	/// every level have none.
+	#[pallet::storage]
+	#[pallet::getter(fn account_level)]
+	pub(super) type AccountLevel<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		u8,
+		OptionQuery
+		>;
+
+	/// Fee-free calls of each account in the current block.
+	#[pallet::storage]
+	#[pallet::getter(fn block_feeless_calls)]
+	pub(super) type BlockFeelessCalls<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery
+		>;
+
+	/// Weight of the fee-free calls in the current block.
+	#[pallet::storage]
+	#[pallet::getter(fn block_feeless_weight)]
+	pub(super) type BlockFeelessWeight<T: Config> = StorageValue<_, Weight, ValueQuery>;
+
+	/// Total fee waived by `feeless_call` in the current period.
+	#[pallet::storage]
+	#[pallet::getter(fn current_subsidy)]
+	pub(super) type CurrentSubsidy<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Total fee waived by `feeless_call` in each closed period, keyed by the period start block.
+	#[pallet::storage]
+	#[pallet::getter(fn period_subsidy)]
+	pub(super) type PeriodSubsidy<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BalanceOf<T>,
+		ValueQuery
+		>;
+
+	/// Total fee waived by `feeless_call` since genesis.
+	#[pallet::storage]
+	#[pallet::getter(fn total_subsidy)]
+	pub(super) type TotalSubsidy<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::type_value]
+	pub(super) fn DefaultRewardPerPeriod<T: Config>() -> BalanceOf<T> {
+		T::RewardPerPeriod::get()
+	}
+
+	/// Reward shared by stakers over each period. It accrues block by block, pro rata to the
+	/// stake of each block.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_per_period)]
+	pub(super) type RewardPerPeriod<T: Config> =
+		StorageValue<_, BalanceOf<T>, ValueQuery, DefaultRewardPerPeriod<T>>;
+
+	/// Total BIC staked.
+	#[pallet::storage]
+	#[pallet::getter(fn total_staked)]
+	pub(super) type TotalStaked<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Reward per unit of stake accrued since genesis, scaled by `REWARD_PRECISION`.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_per_stake)]
+	pub(super) type RewardPerStake<T: Config> = StorageValue<_, u128, ValueQuery>;
+
+	/// Block up to which rewards have accrued into `RewardPerStake`.
+	#[pallet::storage]
+	#[pallet::getter(fn rewards_accrued_at)]
+	pub(super) type RewardsAccruedAt<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// Rewards accrued in the current period.
+	#[pallet::storage]
+	#[pallet::getter(fn current_rewards)]
+	pub(super) type CurrentRewards<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Rewards accrued or credited and not claimed yet, which the reward pot must keep.
+	#[pallet::storage]
+	#[pallet::getter(fn rewards_owed)]
+	pub(super) type RewardsOwed<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// `RewardPerStake` at the last settlement of each staker. The stake earned the difference
+	/// since.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_per_stake_paid)]
+	pub(super) type RewardPerStakePaid<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		u128,
+		ValueQuery
+		>;
+
+	/// Settled rewards of each account, not claimed yet.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_rewards)]
+	pub(super) type PendingRewards<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery
+		>;
+
+	/// Refill of the stakers for the current period, while some are left to refill.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_refill)]
+	pub(super) type PendingRefill<T: Config> = StorageValue<_, Refill<BalanceOf<T>>, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		PeriodForced(T::BlockNumber),
 		Stake(T::AccountId, BalanceOf<T>),
 		UnstakeAll(T::AccountId),
-		BandwidthSpent(T::AccountId, BalanceOf<T>)
+		BandwidthSpent(T::AccountId, BalanceOf<T>),
+		/// A period was closed with the total fee waived during it. \[period_start, subsidy\]
+		PeriodSubsidy(T::BlockNumber, BalanceOf<T>),
+		/// A staking level was set. \[level, bic_locked, bandwidth\]
+		StakingLevelSet(u8, BalanceOf<T>, BalanceOf<T>),
+		/// A staking level was removed. \[level\]
+		StakingLevelRemoved(u8),
+		/// The period length was changed. \[period\]
+		PeriodSet(T::BlockNumber),
+		/// A staker moved to another staking level, or to none. \[account, old, new\]
+		LevelChanged(T::AccountId, Option<u8>, Option<u8>),
+		/// The bandwidth of a staker was refilled for a new period. \[account, bandwidth\]
+		BandwidthRefilled(T::AccountId, BalanceOf<T>),
+		/// `feeless_call` could not waive the fee and charged it to the balance. \[account, fee\]
+		FeeCharged(T::AccountId, BalanceOf<T>),
+		/// The reward per period was changed. \[reward\]
+		RewardPerPeriodSet(BalanceOf<T>),
+		/// Every staker was refilled for a new period, with the rewards accrued during the last
+		/// one and its unused bandwidth bonus credited. \[reward, bonus\]
+		RewardsDistributed(BalanceOf<T>, BalanceOf<T>),
+		/// Staking rewards were paid out. \[account, amount\]
+		RewardsClaimed(T::AccountId, BalanceOf<T>)
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
-		NotAStaker
+		NotAStaker,
+		/// Free balance, locked or not, is below the requested stake.
+		InsufficientBalance,
+		/// No staking level exists at this index.
+		UnknownStakingLevel,
+		/// The period length must be greater than zero.
+		ZeroPeriod,
+		/// The call cannot be dispatched through `feeless_call`.
+		CallNotAllowed,
+		/// Neither bandwidth nor free balance can pay the fee of the call.
+		CannotPayFee,
+		/// The account has no staking rewards to claim.
+		NoRewards,
+		/// The reward pot has nothing left to pay rewards with.
+		RewardPotEmpty,
+		/// Stakers are still being refilled for the current period.
+		RefillInProgress
 	}
 
 	#[pallet::genesis_config]
@@ -242,40 +788,534 @@ impl<T: Config> Pallet<T> {
 		);
 	}
 
-	fn init_stake_new_period() {
-		let mut level_keys = StakingLevelMap::<T>::iter_keys().collect::<Vec<_>>();
-		level_keys.sort();
-		level_keys.reverse();
-		let staking_account_keys = StakingMap::<T>::iter_keys().collect::<Vec<_>>();
+	/// Start a period: take the rewards accrued in the last one and refill the first
+	/// `MaxRefillsPerBlock` stakers. `on_initialize` refills the rest, as many per block.
+	fn start_period() {
+		Self::accrue_rewards();
+		PendingRefill::<T>::put(Refill {
+			last_key: None,
+			total_staked: Self::total_staked(),
+			reward: CurrentRewards::<T>::take(),
+			bonus: Zero::zero(),
+		});
+		Self::refill_stakers();
+	}
 
-		for account in staking_account_keys.iter() {
-			let account_staking = Self::get_stake(&account);
+	/// Refill the next `MaxRefillsPerBlock` stakers of the pending refill, and end it once no
+	/// staker is left. Returns the weight used.
+	fn refill_stakers() -> Weight {
+		let mut refill = match Self::pending_refill() {
+			Some(refill) => refill,
+			None => return T::DbWeight::get().reads(1),
+		};
+		let mut levels = StakingLevelMap::<T>::iter().collect::<Vec<_>>();
+		levels.sort_by_key(|(level, _)| *level);
+		levels.reverse();
 
-			for level in level_keys.iter() {
-				let staking_level = Self::get_staking_level(level);
-				let bic_locked = staking_level.bic_locked;
-				let bandwidth = staking_level.bandwidth;
+		let limit = T::MaxRefillsPerBlock::get();
+		let mut stakers = match refill.last_key.take() {
+			Some(key) => StakingMap::<T>::iter_from(key),
+			None => StakingMap::<T>::iter(),
+		};
+		let page = stakers.by_ref().take(limit as usize).collect::<Vec<_>>();
+		let done = (page.len() as u32) < limit || stakers.next().is_none();
+
+		for (account, stake) in page.iter() {
+			let bonus = Self::refill_staker(account, *stake, refill.total_staked, &levels);
+			refill.bonus = refill.bonus.saturating_add(bonus);
+		}
 
-				if account_staking >= bic_locked {
-					BandwidthMap::<T>::insert(&account, bandwidth);
-					break;
+		if done {
+			PendingRefill::<T>::kill();
+			if !refill.reward.is_zero() || !refill.bonus.is_zero() {
+				Self::deposit_event(Event::RewardsDistributed(refill.reward, refill.bonus));
+			}
+		} else {
+			refill.last_key =
+				page.last().map(|(account, _)| StakingMap::<T>::hashed_key_for(account));
+			PendingRefill::<T>::put(refill);
+		}
+
+		T::DbWeight::get()
+			.reads_writes((levels.len() as Weight).saturating_add(2), 1)
+			.saturating_add(Self::refill_weight().saturating_mul(page.len() as Weight))
+	}
+
+	/// Credit `account` with its unused bandwidth bonus, then refill its bandwidth and set its
+	/// level for the new period. Returns the bonus.
+	fn refill_staker(
+		account: &T::AccountId,
+		stake: BalanceOf<T>,
+		total_staked: BalanceOf<T>,
+		levels: &[(u8, StakingLevel<BalanceOf<T>>)],
+	) -> BalanceOf<T> {
+		// Before the refill, while the bandwidth left unused in the period is known.
+		let bonus = Self::credit_bandwidth_bonus(account);
+
+		// The highest level the stake reaches.
+		let reached = levels.iter().find(|(_, level)| stake >= level.bic_locked);
+		let bandwidth =
+			T::BandwidthCurve::bandwidth(stake, total_staked, reached.map(|(_, level)| level));
+
+		if bandwidth.is_zero() {
+			// Bandwidth left from a period the stake no longer earns any in.
+			BandwidthMap::<T>::remove(account);
+			BandwidthUsage::<T>::remove(account);
+		} else {
+			BandwidthMap::<T>::insert(account, bandwidth);
+			Self::deposit_event(Event::BandwidthRefilled(account.clone(), bandwidth));
+		}
+		Self::set_account_level(account, reached.map(|(level, _)| *level));
+
+		bonus
+	}
+
+	/// Weight of refilling one staker: its stake, bandwidth, level and bonus.
+	fn refill_weight() -> Weight {
+		T::DbWeight::get().reads_writes(7, 6)
+	}
+
+	/// Weight of starting a period in `on_finalize`, its first refills included.
+	pub fn period_start_weight() -> Weight {
+		let refills = T::MaxRefillsPerBlock::get() as Weight;
+		T::DbWeight::get()
+			.reads_writes(8, 8)
+			.saturating_add(Self::refill_weight().saturating_mul(refills))
+	}
+
+	/// Check a `feeless_call` of `call` by `account` can be paid for, with bandwidth or
+	/// balance. In a block, the bandwidth only counts within the limits of the block, and a call
+	/// that only lacks room in them is left for a later block.
+	///
+	/// Calls paid with bandwidth get no priority and stay valid until the period ends and
+	/// bandwidth is recomputed. Calls paid from the balance get the priority of their fee.
+	pub fn validate_feeless(
+		account: &T::AccountId,
+		call: &<T as Config>::Call,
+		len: usize,
+		in_block: bool,
+	) -> TransactionValidity {
+		let (max_weight, max_fee) = Self::feeless_call_cost(call);
+		Self::validate_feeless_cost(account, max_weight, max_fee, len, in_block)
+	}
+
+	/// Validate the `feeless_call`s nested in `call`, if it is a wrapper of nothing else. The
+	/// weights and fees of the calls of each account are added up and checked together.
+	pub fn validate_nested_feeless(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+		len: usize,
+		in_block: bool,
+	) -> TransactionValidity {
+		let calls = match T::NestedFeelessCalls::nested_feeless_calls(who, call) {
+			Some(calls) => calls,
+			None => return Ok(ValidTransaction::default()),
+		};
+
+		let mut costs: Vec<(T::AccountId, Weight, BalanceOf<T>)> = Vec::new();
+		for (account, call) in calls {
+			let (weight, fee) = Self::feeless_call_cost(&call);
+			match costs.iter_mut().find(|(cost_account, ..)| cost_account == &account) {
+				Some((_, total_weight, total_fee)) => {
+					*total_weight = total_weight.saturating_add(weight);
+					*total_fee = total_fee.saturating_add(fee);
 				}
+				None => costs.push((account, weight, fee)),
 			}
 		}
+		costs.into_iter().try_fold(ValidTransaction::default(), |valid, (account, weight, fee)| {
+			let account_valid = Self::validate_feeless_cost(&account, weight, fee, len, in_block)?;
+			Ok(valid.combine_with(account_valid))
+		})
+	}
+
+	/// Weight and fee of a call dispatched through `feeless_call`, at most.
+	fn feeless_call_cost(call: &<T as Config>::Call) -> (Weight, BalanceOf<T>) {
+		let max_weight = call.get_dispatch_info().weight.saturating_add(FEELESS_CALL_OVERHEAD);
+		let max_fee = T::TxPayment::estimate_call_fee(
+			call,
+			PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes },
+		);
+		(max_weight, max_fee)
+	}
+
+	/// Validate fee-free calls of `max_weight` and `max_fee` in all from `account`.
+	fn validate_feeless_cost(
+		account: &T::AccountId,
+		max_weight: Weight,
+		max_fee: BalanceOf<T>,
+		len: usize,
+		in_block: bool,
+	) -> TransactionValidity {
+
+		let bandwidth_covers = Self::available_bandwidth(account) >= max_fee;
+		let within_limits = !in_block || Self::within_block_limits(account, max_weight);
+		if bandwidth_covers && within_limits {
+			return Ok(ValidTransaction { longevity: Self::bandwidth_longevity(), ..Default::default() });
+		}
+
+		if !Self::can_pay_fee(account, max_fee) {
+			let error = if bandwidth_covers {
+				InvalidTransaction::ExhaustsResources
+			} else {
+				InvalidTransaction::Payment
+			};
+			return Err(error.into());
+		}
+		Ok(ValidTransaction {
+			priority: Self::fee_priority(max_fee, max_weight, len),
+			..Default::default()
+		})
+	}
+
+	/// Priority of a call paying `fee`, derived the way `ChargeTransactionPayment` does: the
+	/// fee times how many such calls would fill a block, by weight or by length.
+	fn fee_priority(fee: BalanceOf<T>, weight: Weight, len: usize) -> TransactionPriority {
+		let max_block_weight = <T as frame_system::Config>::BlockWeights::get().max_block;
+		let weight_saturation = max_block_weight / weight.max(1);
+		let max_block_length =
+			*<T as frame_system::Config>::BlockLength::get().max.get(DispatchClass::Normal);
+		let len_saturation = max_block_length as u64 / (len as u64).max(1);
+		let coefficient = weight_saturation.min(len_saturation).saturated_into::<BalanceOf<T>>();
+		fee.saturating_mul(coefficient).saturated_into::<TransactionPriority>()
+	}
+
+	/// Whether `fee` can be withdrawn from the balance of the account, keeping it alive.
+	fn can_pay_fee(account: &T::AccountId, fee: BalanceOf<T>) -> bool {
+		let new_balance = match T::Currency::free_balance(account).checked_sub(&fee) {
+			Some(balance) if balance >= T::Currency::minimum_balance() => balance,
+			_ => return false,
+		};
+		T::Currency::ensure_can_withdraw(account, fee, WithdrawReasons::TRANSACTION_PAYMENT, new_balance)
+			.is_ok()
+	}
+
+	/// Blocks until the bandwidth of stakers is recomputed, at the end of the period. The
+	/// refill may lower bandwidth, regenerating or not, so a call validated against it must
+	/// be checked again then.
+	fn bandwidth_longevity() -> TransactionLongevity {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let period_end = Self::last_period_block().saturating_add(Self::period_length());
+		period_end.saturating_sub(now).max(One::one()).saturated_into::<TransactionLongevity>()
+	}
+
+	/// Whether another fee-free call of `weight` fits in the limits of the current block.
+	fn within_block_limits(account: &T::AccountId, weight: Weight) -> bool {
+		Self::block_feeless_calls(account) < T::MaxFeelessCallsPerBlock::get() &&
+			Self::block_feeless_weight().saturating_add(weight) <= T::MaxFeelessWeightPerBlock::get()
+	}
+
+	/// Bandwidth the account can spend now.
+	pub fn available_bandwidth(account: &T::AccountId) -> BalanceOf<T> {
+		let bandwidth = Self::get_bandwidth(account);
+		if T::RegenerationWindow::get().is_zero() {
+			return bandwidth;
+		}
+		let now = <frame_system::Pallet<T>>::block_number();
+		bandwidth.saturating_sub(Self::regenerated_usage(account, bandwidth, now))
+	}
+
+	/// Spend `amount` of the bandwidth of the account, once checked it is available.
+	fn spend_bandwidth(account: &T::AccountId, amount: BalanceOf<T>) {
+		let bandwidth = Self::get_bandwidth(account);
+
+		if T::RegenerationWindow::get().is_zero() {
+			BandwidthMap::<T>::insert(account, bandwidth.saturating_sub(amount));
+			return;
+		}
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		let used = Self::regenerated_usage(account, bandwidth, now);
+		BandwidthUsage::<T>::insert(account, (used.saturating_add(amount), now));
+	}
+
+	/// Bandwidth still counted as used at `now`: what was used at the last update, less the
+	/// share of `bandwidth` regenerated since.
+	fn regenerated_usage(
+		account: &T::AccountId,
+		bandwidth: BalanceOf<T>,
+		now: T::BlockNumber,
+	) -> BalanceOf<T> {
+		let (used, last_update) = Self::bandwidth_usage(account);
+		let window = T::RegenerationWindow::get();
+		let elapsed = now.saturating_sub(last_update);
+		if elapsed >= window {
+			return Zero::zero();
+		}
+		used.saturating_sub(Perbill::from_rational(elapsed, window) * bandwidth)
+	}
+
+	/// Record the level of a staker, announcing it when it changes.
+	fn set_account_level(account: &T::AccountId, level: Option<u8>) {
+		let old = AccountLevel::<T>::get(account);
+		if old == level {
+			return;
+		}
+
+		match level {
+			Some(level) => AccountLevel::<T>::insert(account, level),
+			None => AccountLevel::<T>::remove(account),
+		}
+		Self::deposit_event(Event::LevelChanged(account.clone(), old, level));
+	}
+
+	/// Account of the reward pot.
+	pub fn reward_pot() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Rewards the account can claim now, settled or not.
+	pub fn claimable_rewards(account: &T::AccountId) -> BalanceOf<T> {
+		let earned_per_stake = Self::current_reward_per_stake()
+			.saturating_sub(Self::reward_per_stake_paid(account));
+		let earned = Self::stake_rewards(account, earned_per_stake);
+		Self::pending_rewards(account).saturating_add(earned)
+	}
+
+	/// Rewards of the stake of the account for `earned_per_stake`, scaled by
+	/// `REWARD_PRECISION`.
+	fn stake_rewards(account: &T::AccountId, earned_per_stake: u128) -> BalanceOf<T> {
+		let stake = Self::get_stake(account).saturated_into::<u128>();
+		multiply_by_rational(stake, earned_per_stake, REWARD_PRECISION)
+			.unwrap_or(u128::MAX)
+			.saturated_into()
+	}
 
+	/// `RewardPerStake` with the rewards accrued since the last update.
+	fn current_reward_per_stake() -> u128 {
+		let elapsed = <frame_system::Pallet<T>>::block_number()
+			.saturating_sub(Self::rewards_accrued_at());
+		let accrued = Self::reward_per_stake_of(Self::accrued_reward(elapsed));
+		Self::reward_per_stake().saturating_add(accrued)
+	}
+
+	/// Increase of `RewardPerStake` for `reward` shared by the current total stake.
+	fn reward_per_stake_of(reward: BalanceOf<T>) -> u128 {
+		let total_staked = Self::total_staked();
+		if reward.is_zero() || total_staked.is_zero() {
+			return 0;
+		}
+		multiply_by_rational(
+			reward.saturated_into(),
+			REWARD_PRECISION,
+			total_staked.saturated_into(),
+		)
+		.unwrap_or(u128::MAX)
+	}
+
+	/// Reward of `elapsed` blocks at the current rate: none without stake, and no more than the
+	/// pot can fund.
+	fn accrued_reward(elapsed: T::BlockNumber) -> BalanceOf<T> {
+		if Self::total_staked().is_zero() {
+			return Zero::zero();
+		}
+		let reward = multiply_by_rational(
+			Self::reward_per_period().saturated_into(),
+			elapsed.saturated_into(),
+			Self::period_length().saturated_into(),
+		)
+		.unwrap_or(u128::MAX)
+		.saturated_into::<BalanceOf<T>>();
+		reward.min(Self::reward_capacity())
+	}
+
+	/// Rewards the pot can still take on: any amount when rewards are minted, otherwise what it
+	/// holds beyond its existential deposit and the rewards it already owes.
+	fn reward_capacity() -> BalanceOf<T> {
+		if T::MintRewards::get() {
+			return BalanceOf::<T>::max_value();
+		}
+		T::Currency::free_balance(&Self::reward_pot())
+			.saturating_sub(T::Currency::minimum_balance())
+			.saturating_sub(Self::rewards_owed())
+	}
+
+	/// Accrue the rewards of the blocks since the last update to every unit of stake. Rewards
+	/// follow the stake of each block, so stake only earns for the blocks it was staked.
+	fn accrue_rewards() {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let elapsed = now.saturating_sub(Self::rewards_accrued_at());
+		if elapsed.is_zero() {
+			return;
+		}
+		RewardsAccruedAt::<T>::put(now);
+
+		let reward = Self::accrued_reward(elapsed);
+		if reward.is_zero() {
+			return;
+		}
+		RewardPerStake::<T>::mutate(|total| {
+			*total = total.saturating_add(Self::reward_per_stake_of(reward))
+		});
+		CurrentRewards::<T>::mutate(|total| *total = total.saturating_add(reward));
+		Self::fund_rewards(reward);
+	}
+
+	/// Record `amount` of new rewards as owed by the pot, minting it there if rewards are
+	/// minted.
+	fn fund_rewards(amount: BalanceOf<T>) {
+		RewardsOwed::<T>::mutate(|owed| *owed = owed.saturating_add(amount));
+		if T::MintRewards::get() {
+			// The pot keeps its existential deposit, minted along with its first rewards.
+			let pot = Self::reward_pot();
+			let deposit =
+				T::Currency::minimum_balance().saturating_sub(T::Currency::free_balance(&pot));
+			drop(T::Currency::deposit_creating(&pot, amount.saturating_add(deposit)));
+		}
+	}
+
+	/// Add the rewards the stake earned so far to the pending rewards of the account. Done
+	/// before the stake changes, so that rewards are accounted lazily rather than per staker
+	/// at each block.
+	fn settle_rewards(account: &T::AccountId) {
+		Self::accrue_rewards();
+		let reward_per_stake = Self::reward_per_stake();
+		let earned = Self::stake_rewards(
+			account,
+			reward_per_stake.saturating_sub(Self::reward_per_stake_paid(account)),
+		);
+		if !earned.is_zero() {
+			PendingRewards::<T>::mutate(account, |pending| *pending = pending.saturating_add(earned));
+		}
+		RewardPerStakePaid::<T>::insert(account, reward_per_stake);
+	}
+
+	/// Credit the account with the bonus for the bandwidth it left unused, as far as the pot
+	/// can fund it. Returns the bonus.
+	fn credit_bandwidth_bonus(account: &T::AccountId) -> BalanceOf<T> {
+		let bonus_share = T::UnusedBandwidthBonus::get();
+		if bonus_share.is_zero() {
+			return Zero::zero();
+		}
+		let bonus = (bonus_share * Self::available_bandwidth(account)).min(Self::reward_capacity());
+		if !bonus.is_zero() {
+			PendingRewards::<T>::mutate(account, |pending| *pending = pending.saturating_add(bonus));
+			Self::fund_rewards(bonus);
+		}
+		bonus
+	}
+
+	/// Pay the rewards of `staker` out of the reward pot, as far as the pot covers them.
+	fn pay_rewards(staker: &T::AccountId) -> DispatchResult {
+		let claimable = Self::claimable_rewards(staker);
+		ensure!(!claimable.is_zero(), Error::<T>::NoRewards);
+
+		let pot = Self::reward_pot();
+		let available = T::Currency::free_balance(&pot).saturating_sub(T::Currency::minimum_balance());
+		let paid = claimable.min(available);
+		ensure!(!paid.is_zero(), Error::<T>::RewardPotEmpty);
+
+		// Before the transfer, while the pot still holds what it accrues against.
+		if StakingMap::<T>::contains_key(staker) {
+			Self::settle_rewards(staker);
+		}
+		T::Currency::transfer(&pot, staker, paid, ExistenceRequirement::KeepAlive)?;
+		let left = Self::pending_rewards(staker).saturating_sub(paid);
+		if left.is_zero() {
+			PendingRewards::<T>::remove(staker);
+		} else {
+			PendingRewards::<T>::insert(staker, left);
+		}
+		RewardsOwed::<T>::mutate(|owed| *owed = owed.saturating_sub(paid));
+		Self::deposit_event(Event::RewardsClaimed(staker.clone(), paid));
+
+		Ok(())
+	}
+
+	/// Archive the fee waived during the period that is ending and start a new tally.
+	fn close_subsidy_period() {
+		let period_start = Self::last_period_block();
+		let subsidy = CurrentSubsidy::<T>::take();
+		PeriodSubsidy::<T>::insert(period_start, subsidy);
+		TotalSubsidy::<T>::mutate(|total| *total = total.saturating_add(subsidy));
+		Self::deposit_event(Event::PeriodSubsidy(period_start, subsidy));
 	}
 
 	fn finalize_block(now: T::BlockNumber) {
+		if Self::pending_refill().is_some() {
+			// The next period starts once every staker is refilled for this one.
+			return;
+		}
+
 		if now == Self::last_period_block() {
-			Self::init_stake_new_period();
+			Self::start_period();
 			return;
 		}
 
-		if Self::last_period_block() + T::Period::get() != now {
+		if now < Self::last_period_block().saturating_add(Self::period_length()) {
 			return;
 		}
 
+		Self::close_subsidy_period();
 		LPBlock::<T>::put(now);
-		Self::init_stake_new_period();
+		Self::start_period();
+	}
+}
+
+/// Validates `feeless_call` in the transaction pool, alone or nested in the wrappers found by
+/// `Config::NestedFeelessCalls`. The call pays no transaction fee, so without this check calls
+/// that cannot be paid for at all would only fail in a block.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckFeeless<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckFeeless<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckFeeless<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckFeeless<T> {
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckFeeless")
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckFeeless<T>
+where
+	<T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "CheckFeeless";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		match call.is_sub_type() {
+			Some(Call::feeless_call { call }) =>
+				Pallet::<T>::validate_feeless(who, call, len, false),
+			_ => Pallet::<T>::validate_nested_feeless(who, call, len, false),
+		}
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		match call.is_sub_type() {
+			Some(Call::feeless_call { call }) =>
+				Pallet::<T>::validate_feeless(who, call, len, true).map(|_| ()),
+			_ => Pallet::<T>::validate_nested_feeless(who, call, len, true).map(|_| ()),
+		}
 	}
 }
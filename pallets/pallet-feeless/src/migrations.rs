@@ -0,0 +1,89 @@
+//! Storage migrations of the feeless pallet.
+
+use super::*;
+use frame_support::traits::GetStorageVersion;
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+/// Run every migration the on-chain storage version has not seen yet.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if Pallet::<T>::on_chain_storage_version() < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	if Pallet::<T>::on_chain_storage_version() < 2 {
+		weight = weight.saturating_add(v2::migrate::<T>());
+		StorageVersion::new(2).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	weight
+}
+
+/// Records the total stake, which no migration may change.
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+	Pallet::<T>::set_temp_storage(total_stake::<T>(), "total_stake");
+	Ok(())
+}
+
+/// Checks the storage version was bumped, the total stake is unchanged and every stake is
+/// still backed by the balance of its staker.
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+	ensure!(
+		Pallet::<T>::on_chain_storage_version() == STORAGE_VERSION,
+		"storage version not updated"
+	);
+	let before: BalanceOf<T> = Pallet::<T>::get_temp_storage("total_stake")
+		.ok_or("total stake not recorded before the upgrade")?;
+	ensure!(before == total_stake::<T>(), "total stake changed by the upgrade");
+	ensure!(before == Pallet::<T>::total_staked(), "total stake not recorded");
+	for (staker, stake) in StakingMap::<T>::iter() {
+		ensure!(T::Currency::total_balance(&staker) >= stake, "stake exceeds the staker balance");
+	}
+	Ok(())
+}
+
+#[cfg(feature = "try-runtime")]
+fn total_stake<T: Config>() -> BalanceOf<T> {
+	StakingMap::<T>::iter_values().fold(Zero::zero(), |total: BalanceOf<T>, stake| {
+		total.saturating_add(stake)
+	})
+}
+
+/// Version 1 holds stakes with a lock instead of a reserve.
+pub mod v1 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut stakers: Weight = 0;
+		for (staker, stake) in StakingMap::<T>::iter() {
+			T::Currency::unreserve(&staker, stake);
+			T::Currency::set_lock(STAKING_ID, &staker, stake, WithdrawReasons::all());
+			stakers = stakers.saturating_add(1);
+		}
+		T::DbWeight::get().reads_writes(stakers, stakers.saturating_mul(3))
+	}
+}
+
+/// Version 2 keeps the total stake in storage, and rewards accrue from the upgrade on.
+pub mod v2 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut stakers: Weight = 0;
+		let mut total: BalanceOf<T> = Zero::zero();
+		for stake in StakingMap::<T>::iter_values() {
+			total = total.saturating_add(stake);
+			stakers = stakers.saturating_add(1);
+		}
+		TotalStaked::<T>::put(total);
+		RewardsAccruedAt::<T>::put(<frame_system::Pallet<T>>::block_number());
+		T::DbWeight::get().reads_writes(stakers, 2)
+	}
+}
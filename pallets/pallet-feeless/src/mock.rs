@@ -1,15 +1,28 @@
-use crate as pallet_template;
-use frame_support::parameter_types;
+use crate as pallet_feeless;
+use crate::LevelBandwidth;
+use frame_support::{
+	parameter_types,
+	traits::{EstimateCallFee, Everything, GenesisBuild},
+	weights::{PostDispatchInfo, Weight},
+	PalletId,
+};
 use frame_system as system;
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
+pub const BIC: u128 = 1_000_000_000_000_000_000;
+
+/// Fee of every call in the mock, whatever its weight.
+pub const CALL_FEE: u128 = 1_000;
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -18,7 +31,8 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Feeless: pallet_feeless::{Pallet, Call, Config<T>, Storage, Event<T>},
 	}
 );
 
@@ -28,7 +42,7 @@ parameter_types! {
 }
 
 impl system::Config for Test {
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = Everything;
 	type BlockWeights = ();
 	type BlockLength = ();
 	type DbWeight = ();
@@ -45,7 +59,7 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u128>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -53,11 +67,78 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
-impl pallet_template::Config for Test {
+parameter_types! {
+	pub const ExistentialDeposit: u128 = 1;
+	pub const MaxLocks: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = MaxLocks;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u128;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+pub struct FixedCallFee;
+impl EstimateCallFee<Call, u128> for FixedCallFee {
+	fn estimate_call_fee(_call: &Call, _post_info: PostDispatchInfo) -> u128 {
+		CALL_FEE
+	}
+}
+
+parameter_types! {
+	pub const Period: u64 = 10;
+	pub static RegenerationWindow: u64 = 0;
+	pub static MaxFeelessCallsPerBlock: u32 = 10;
+	pub static MaxFeelessWeightPerBlock: Weight = Weight::MAX;
+	pub const FeelessPalletId: PalletId = PalletId(*b"py/fless");
+	pub const RewardPerPeriod: u128 = 0;
+	pub static MintRewards: bool = true;
+	pub static UnusedBandwidthBonus: Perbill = Perbill::zero();
+	pub static MaxRefillsPerBlock: u32 = 10;
+}
+
+impl pallet_feeless::Config for Test {
 	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type Period = Period;
+	type TxPayment = FixedCallFee;
+	type ForceOrigin = EnsureRoot<u64>;
+	type CallFilter = Everything;
+	type NestedFeelessCalls = ();
+	type BandwidthCurve = LevelBandwidth;
+	type RegenerationWindow = RegenerationWindow;
+	type MaxFeelessCallsPerBlock = MaxFeelessCallsPerBlock;
+	type MaxFeelessWeightPerBlock = MaxFeelessWeightPerBlock;
+	type OnFeeCharged = ();
+	type PalletId = FeelessPalletId;
+	type RewardPerPeriod = RewardPerPeriod;
+	type MintRewards = MintRewards;
+	type UnusedBandwidthBonus = UnusedBandwidthBonus;
+	type MaxRefillsPerBlock = MaxRefillsPerBlock;
+	type WeightInfo = ();
 }
 
-// Build genesis storage according to the mock runtime.
+// Build genesis storage according to the mock runtime. Accounts 1 to 3 hold 100_000 BIC and
+// the default levels need 10_000 and 70_000 BIC.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100_000 * BIC), (2, 100_000 * BIC), (3, 100_000 * BIC)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	pallet_feeless::GenesisConfig::<Test>::default()
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
@@ -1,20 +1,542 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	mock::*, BandwidthCurve, CheckFeeless, Error, Event as FeelessEvent, ProportionalBandwidth,
+	StakingLevel,
+};
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	traits::{Currency, Get, OnFinalize, OnInitialize},
+	weights::{DispatchClass, GetDispatchInfo},
+};
+use sp_runtime::{
+	Perbill,
+	traits::{BadOrigin, SignedExtension},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+
+/// Start a period at the current block, the way `finalize_block` does when a period ends.
+fn new_period() {
+	assert_ok!(Feeless::force_period(Origin::root()));
+	Feeless::on_finalize(System::block_number());
+}
+
+fn remark() -> Box<Call> {
+	Box::new(Call::System(frame_system::Call::remark { remark: vec![] }))
+}
+
+/// Account 1 stakes into a level worth two calls, with the period starting at block 1.
+fn stake_for_two_calls() {
+	assert_ok!(Feeless::set_staking_level(Origin::root(), 1, 10_000 * BIC, 2 * CALL_FEE));
+	assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+	new_period();
+}
+
+/// Make a feeless remark from account 1, telling whether its fee was waived.
+fn feeless_remark_waived() -> bool {
+	let balance = Balances::free_balance(1);
+	assert_ok!(Feeless::feeless_call(Origin::signed(1), remark()));
+	Balances::free_balance(1) == balance
+}
+
+fn has_event(event: FeelessEvent<Test>) -> bool {
+	System::events().iter().any(|record| record.event == Event::Feeless(event.clone()))
+}
+
+#[test]
+fn staker_is_assigned_the_level_its_stake_reaches() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		new_period();
+
+		assert_eq!(Feeless::account_level(1), Some(1));
+		assert_eq!(Feeless::get_bandwidth(1), BIC);
+		assert!(has_event(FeelessEvent::LevelChanged(1, None, Some(1))));
+		assert!(has_event(FeelessEvent::BandwidthRefilled(1, BIC)));
+	});
+}
+
+#[test]
+fn moving_up_a_level_is_announced() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		new_period();
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 60_000 * BIC));
+		new_period();
+
+		assert_eq!(Feeless::account_level(1), Some(2));
+		assert_eq!(Feeless::get_bandwidth(1), 3 * BIC);
+		assert!(has_event(FeelessEvent::LevelChanged(1, Some(1), Some(2))));
+	});
+}
+
+#[test]
+fn same_level_refills_without_level_change() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		new_period();
+		System::reset_events();
+		new_period();
+
+		assert_eq!(Feeless::account_level(1), Some(1));
+		assert!(has_event(FeelessEvent::BandwidthRefilled(1, BIC)));
+		assert!(!has_event(FeelessEvent::LevelChanged(1, Some(1), Some(1))));
+	});
+}
+
+#[test]
+fn falling_below_every_level_clears_bandwidth() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		new_period();
+		assert_ok!(Feeless::set_staking_level(Origin::root(), 1, 20_000 * BIC, BIC));
+		new_period();
+
+		assert_eq!(Feeless::account_level(1), None);
+		assert_eq!(Feeless::get_bandwidth(1), 0);
+		assert!(has_event(FeelessEvent::LevelChanged(1, Some(1), None)));
+	});
+}
+
+#[test]
+fn stake_below_every_level_gets_no_level() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), BIC));
+		new_period();
+
+		assert_eq!(Feeless::account_level(1), None);
+		assert_eq!(Feeless::get_bandwidth(1), 0);
+	});
+}
+
+#[test]
+fn unstaking_clears_the_level() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		new_period();
+		assert_ok!(Feeless::unstake_bic(Origin::signed(1)));
+
+		assert_eq!(Feeless::account_level(1), None);
+		assert_eq!(Feeless::get_bandwidth(1), 0);
+		assert!(has_event(FeelessEvent::LevelChanged(1, Some(1), None)));
+		assert_noop!(Feeless::unstake_bic(Origin::signed(1)), Error::<Test>::NotAStaker);
+	});
+}
+
+#[test]
+fn removing_a_level_drops_its_stakers_at_the_next_period() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		new_period();
+		assert_noop!(Feeless::remove_staking_level(Origin::signed(1), 1), BadOrigin);
+		assert_noop!(
+			Feeless::remove_staking_level(Origin::root(), 7),
+			Error::<Test>::UnknownStakingLevel
+		);
+
+		assert_ok!(Feeless::remove_staking_level(Origin::root(), 1));
+		assert!(has_event(FeelessEvent::StakingLevelRemoved(1)));
+		assert_eq!(Feeless::account_level(1), Some(1));
+		new_period();
+
+		assert_eq!(Feeless::account_level(1), None);
+		assert_eq!(Feeless::get_bandwidth(1), 0);
+		assert!(has_event(FeelessEvent::LevelChanged(1, Some(1), None)));
+	});
+}
+
+#[test]
+fn set_period_changes_when_the_current_period_ends() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Feeless::set_period(Origin::signed(1), 5), BadOrigin);
+		assert_noop!(Feeless::set_period(Origin::root(), 0), Error::<Test>::ZeroPeriod);
+
+		assert_ok!(Feeless::set_period(Origin::root(), 5));
+		assert!(has_event(FeelessEvent::PeriodSet(5)));
+		assert_eq!(Feeless::period_length(), 5);
+
+		let start = Feeless::last_period_block();
+		System::set_block_number(start + 4);
+		Feeless::on_finalize(start + 4);
+		assert_eq!(Feeless::last_period_block(), start);
+		System::set_block_number(start + 5);
+		Feeless::on_finalize(start + 5);
+		assert_eq!(Feeless::last_period_block(), start + 5);
+	});
+}
+
+#[test]
+fn set_period_keeps_the_rewards_accrued_at_the_old_length() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::set_reward_per_period(Origin::root(), 4 * BIC));
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		// Five blocks at 4 BIC per 10 blocks, then five at 4 BIC per 20 blocks.
+		System::set_block_number(6);
+		assert_ok!(Feeless::set_period(Origin::root(), 20));
+		System::set_block_number(11);
+
+		assert_eq!(Feeless::claimable_rewards(&1), 3 * BIC);
+	});
+}
+
+parameter_types! {
+	pub const Budget: u128 = 1_000;
+}
+
+#[test]
+fn proportional_bandwidth_shares_the_budget_by_stake() {
+	type Curve = ProportionalBandwidth<Budget>;
+	let bandwidth = <Curve as BandwidthCurve<u128>>::bandwidth;
+	let level = StakingLevel { bic_locked: 10, bandwidth: 5 };
+
+	assert_eq!(bandwidth(25, 100, None), 250);
+	assert_eq!(bandwidth(69, 100, Some(&level)), 690);
+	assert_eq!(bandwidth(100, 100, Some(&level)), 1_000);
+	assert_eq!(bandwidth(0, 0, None), 0);
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn bandwidth_is_refilled_only_at_period_start_without_window() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		stake_for_two_calls();
+		assert!(feeless_remark_waived());
+		assert!(feeless_remark_waived());
+		assert!(!feeless_remark_waived());
+
+		System::set_block_number(9);
+		assert_eq!(Feeless::available_bandwidth(&1), 0);
+		new_period();
+		assert_eq!(Feeless::available_bandwidth(&1), 2 * CALL_FEE);
+	});
+}
+
+#[test]
+fn spent_bandwidth_regenerates_over_the_window() {
+	new_test_ext().execute_with(|| {
+		RegenerationWindow::set(100);
+		stake_for_two_calls();
+		assert!(feeless_remark_waived());
+		assert!(feeless_remark_waived());
+		assert!(!feeless_remark_waived());
+		assert_eq!(Feeless::available_bandwidth(&1), 0);
+
+		System::set_block_number(51);
+		assert_eq!(Feeless::available_bandwidth(&1), CALL_FEE);
+		assert!(feeless_remark_waived());
+		assert_eq!(Feeless::available_bandwidth(&1), 0);
+
+		System::set_block_number(151);
+		assert_eq!(Feeless::available_bandwidth(&1), 2 * CALL_FEE);
+	});
+}
+
+#[test]
+fn fee_is_charged_without_bandwidth() {
+	new_test_ext().execute_with(|| {
+		assert!(!feeless_remark_waived());
+		assert_eq!(Balances::free_balance(1), 100_000 * BIC - CALL_FEE);
+		assert!(has_event(FeelessEvent::FeeCharged(1, CALL_FEE)));
+	});
+}
+
+#[test]
+fn waived_fees_are_tracked_as_subsidy() {
+	new_test_ext().execute_with(|| {
+		stake_for_two_calls();
+		assert!(feeless_remark_waived());
+		assert!(feeless_remark_waived());
+		// Charged to the balance, so not subsidized.
+		assert!(!feeless_remark_waived());
+		assert_eq!(Feeless::current_subsidy(), 2 * CALL_FEE);
+		assert_eq!(Feeless::total_subsidy(), 0);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn subsidy_is_reported_when_a_period_closes() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		stake_for_two_calls();
+		assert!(feeless_remark_waived());
+		assert!(feeless_remark_waived());
+
+		System::set_block_number(11);
+		Feeless::on_finalize(11);
+		assert_eq!(Feeless::current_subsidy(), 0);
+		assert_eq!(Feeless::period_subsidy(1), 2 * CALL_FEE);
+		assert_eq!(Feeless::total_subsidy(), 2 * CALL_FEE);
+		assert!(has_event(FeelessEvent::PeriodSubsidy(1, 2 * CALL_FEE)));
+
+		// Forcing a period closes the current one as well.
+		assert!(feeless_remark_waived());
+		System::set_block_number(12);
+		new_period();
+		assert_eq!(Feeless::period_subsidy(11), CALL_FEE);
+		assert_eq!(Feeless::total_subsidy(), 3 * CALL_FEE);
+		assert!(has_event(FeelessEvent::PeriodSubsidy(11, CALL_FEE)));
+	});
+}
+
+#[test]
+fn call_is_rejected_when_fee_cannot_be_paid() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Feeless::feeless_call(Origin::signed(4), remark()),
+			Error::<Test>::CannotPayFee
+		);
+	});
+}
+
+#[test]
+fn fee_free_calls_per_account_are_capped_per_block() {
+	new_test_ext().execute_with(|| {
+		MaxFeelessCallsPerBlock::set(1);
+		stake_for_two_calls();
+		assert!(feeless_remark_waived());
+		assert!(!feeless_remark_waived());
+
+		System::set_block_number(2);
+		Feeless::on_initialize(2);
+		assert!(feeless_remark_waived());
+	});
+}
+
+#[test]
+fn fee_free_weight_is_capped_per_block() {
+	new_test_ext().execute_with(|| {
+		let call_weight = remark().get_dispatch_info().weight + 10_000;
+		MaxFeelessWeightPerBlock::set(call_weight);
+		stake_for_two_calls();
+		assert_ok!(Feeless::stake_bic(Origin::signed(2), 10_000 * BIC));
+		new_period();
+
+		assert!(feeless_remark_waived());
+		// The budget is shared by every account.
+		let balance = Balances::free_balance(2);
+		assert_ok!(Feeless::feeless_call(Origin::signed(2), remark()));
+		assert_eq!(Balances::free_balance(2), balance - CALL_FEE);
+
+		System::set_block_number(2);
+		Feeless::on_initialize(2);
+		assert_eq!(Feeless::block_feeless_weight(), 0);
+		assert!(feeless_remark_waived());
+	});
+}
+
+fn feeless_remark_call() -> Call {
+	Call::Feeless(crate::Call::feeless_call { call: remark() })
+}
+
+/// Encoded length the remark transactions are validated with.
+const CALL_LEN: usize = 100;
+
+fn pool_validity(who: u64) -> Result<(u64, u64), TransactionValidityError> {
+	let call = feeless_remark_call();
+	CheckFeeless::<Test>::new()
+		.validate(&who, &call, &call.get_dispatch_info(), CALL_LEN)
+		.map(|valid| (valid.priority, valid.longevity))
+}
+
+fn block_validity(who: u64) -> Result<(), TransactionValidityError> {
+	let call = feeless_remark_call();
+	CheckFeeless::<Test>::new().pre_dispatch(&who, &call, &call.get_dispatch_info(), CALL_LEN)
+}
+
+#[test]
+fn pool_rejects_calls_without_bandwidth_or_balance() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(pool_validity(4), Err(InvalidTransaction::Payment.into()));
+	});
+}
+
+#[test]
+fn calls_paid_from_balance_get_the_priority_of_their_fee() {
+	new_test_ext().execute_with(|| {
+		// Like `ChargeTransactionPayment`, the fee times how many such calls fill a block.
+		let max_weight = <Test as frame_system::Config>::BlockWeights::get().max_block;
+		let max_length =
+			*<Test as frame_system::Config>::BlockLength::get().max.get(DispatchClass::Normal);
+		let per_block = (max_weight / feeless_remark_call().get_dispatch_info().weight)
+			.min(max_length as u64 / CALL_LEN as u64);
+		assert_eq!(pool_validity(1), Ok((CALL_FEE as u64 * per_block, u64::MAX)));
+	});
+}
+
+#[test]
+fn calls_paid_with_bandwidth_get_no_priority_until_the_period_ends() {
+	new_test_ext().execute_with(|| {
+		stake_for_two_calls();
+		assert_eq!(pool_validity(1), Ok((0, 10)));
+
+		// Regenerating bandwidth is recomputed at the end of the period all the same.
+		RegenerationWindow::set(100);
+		assert_eq!(pool_validity(1), Ok((0, 10)));
+		System::set_block_number(8);
+		assert_eq!(pool_validity(1), Ok((0, 3)));
+	});
+}
+
+#[test]
+fn calls_beyond_block_limits_wait_for_a_later_block() {
+	new_test_ext().execute_with(|| {
+		// Account 5 stakes all it has, so it cannot pay fees.
+		Balances::make_free_balance_be(&5, 10_000 * BIC);
+		assert_ok!(Feeless::set_staking_level(Origin::root(), 1, 10_000 * BIC, 2 * CALL_FEE));
+		assert_ok!(Feeless::stake_bic(Origin::signed(5), 10_000 * BIC));
+		new_period();
+		MaxFeelessCallsPerBlock::set(0);
+
+		assert!(pool_validity(5).is_ok());
+		assert_eq!(block_validity(5), Err(InvalidTransaction::ExhaustsResources.into()));
+		assert_eq!(block_validity(1), Ok(()));
+	});
+}
+
+#[test]
+fn rewards_are_shared_pro_rata_to_stake() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::set_reward_per_period(Origin::root(), 4 * BIC));
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		assert_ok!(Feeless::stake_bic(Origin::signed(2), 30_000 * BIC));
+		System::set_block_number(11);
+		new_period();
+
+		assert_eq!(Feeless::claimable_rewards(&1), BIC);
+		assert_eq!(Feeless::claimable_rewards(&2), 3 * BIC);
+		assert!(has_event(FeelessEvent::RewardsDistributed(4 * BIC, 0)));
+
+		assert_ok!(Feeless::claim_rewards(Origin::signed(1)));
+		assert_eq!(Balances::free_balance(1), 100_001 * BIC);
+		assert_eq!(Feeless::claimable_rewards(&1), 0);
+		assert!(has_event(FeelessEvent::RewardsClaimed(1, BIC)));
+	});
+}
+
+#[test]
+fn stake_only_earns_for_the_blocks_it_is_staked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::set_reward_per_period(Origin::root(), 4 * BIC));
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		// Account 2 stakes one block before the period ends.
+		System::set_block_number(10);
+		assert_ok!(Feeless::stake_bic(Origin::signed(2), 10_000 * BIC));
+		System::set_block_number(11);
+		new_period();
+
+		assert_eq!(Feeless::claimable_rewards(&1), 38 * BIC / 10);
+		assert_eq!(Feeless::claimable_rewards(&2), 2 * BIC / 10);
+	});
+}
+
+#[test]
+fn forcing_a_period_only_pays_the_elapsed_blocks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::set_reward_per_period(Origin::root(), 4 * BIC));
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		System::set_block_number(6);
+		new_period();
+		new_period();
+
+		assert_eq!(Feeless::claimable_rewards(&1), 2 * BIC);
+		assert_eq!(Feeless::rewards_owed(), 2 * BIC);
+		// Minted, along with the existential deposit of the pot.
+		assert_eq!(Balances::free_balance(Feeless::reward_pot()), 2 * BIC + 1);
+	});
+}
+
+#[test]
+fn rewards_stay_claimable_after_unstaking() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Feeless::set_reward_per_period(Origin::root(), 4 * BIC));
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		System::set_block_number(11);
+		assert_ok!(Feeless::unstake_bic(Origin::signed(1)));
+		assert_ok!(Feeless::stake_bic(Origin::signed(2), 10_000 * BIC));
+		System::set_block_number(21);
+
+		assert_eq!(Feeless::claimable_rewards(&1), 4 * BIC);
+		assert_ok!(Feeless::claim_rewards_for(Origin::signed(3), 1));
+		assert_eq!(Balances::free_balance(1), 100_004 * BIC);
+		assert_noop!(Feeless::claim_rewards(Origin::signed(1)), Error::<Test>::NoRewards);
+	});
+}
+
+#[test]
+fn rewards_not_minted_only_accrue_as_far_as_the_pot_funds_them() {
+	new_test_ext().execute_with(|| {
+		MintRewards::set(false);
+		assert_ok!(Feeless::set_reward_per_period(Origin::root(), 4 * BIC));
+		assert_ok!(Feeless::stake_bic(Origin::signed(1), 10_000 * BIC));
+		System::set_block_number(11);
+		assert_eq!(Feeless::claimable_rewards(&1), 0);
+		assert_noop!(Feeless::claim_rewards(Origin::signed(1)), Error::<Test>::NoRewards);
+
+		// The pot keeps its existential deposit.
+		Balances::make_free_balance_be(&Feeless::reward_pot(), 3 * BIC + 1);
+		System::set_block_number(21);
+		assert_eq!(Feeless::claimable_rewards(&1), 3 * BIC);
+		assert_ok!(Feeless::claim_rewards(Origin::signed(1)));
+		assert_eq!(Balances::free_balance(1), 100_003 * BIC);
+		assert_eq!(Feeless::rewards_owed(), 0);
+
+		System::set_block_number(31);
+		assert_eq!(Feeless::claimable_rewards(&1), 0);
+	});
+}
+
+#[test]
+fn unused_bandwidth_is_converted_into_a_bonus() {
+	new_test_ext().execute_with(|| {
+		UnusedBandwidthBonus::set(Perbill::from_percent(50));
+		stake_for_two_calls();
+		assert!(feeless_remark_waived());
+		new_period();
+
+		assert_eq!(Feeless::claimable_rewards(&1), CALL_FEE / 2);
+		assert!(has_event(FeelessEvent::RewardsDistributed(0, CALL_FEE / 2)));
+	});
+}
+
+#[test]
+fn stakers_are_refilled_over_several_blocks() {
+	new_test_ext().execute_with(|| {
+		MaxRefillsPerBlock::set(2);
+		assert_ok!(Feeless::set_reward_per_period(Origin::root(), 3 * BIC));
+		for staker in 1..=3 {
+			assert_ok!(Feeless::stake_bic(Origin::signed(staker), 10_000 * BIC));
+		}
+		let refilled = || (1..=3).filter(|staker| Feeless::get_bandwidth(staker) == BIC).count();
+
+		System::set_block_number(11);
+		Feeless::on_finalize(11);
+		assert_eq!(refilled(), 2);
+		assert!(Feeless::pending_refill().is_some());
+		assert!(!has_event(FeelessEvent::RewardsDistributed(3 * BIC, 0)));
+
+		System::set_block_number(12);
+		Feeless::on_initialize(12);
+		assert_eq!(refilled(), 3);
+		assert!(Feeless::pending_refill().is_none());
+		assert!(has_event(FeelessEvent::RewardsDistributed(3 * BIC, 0)));
+	});
+}
+
+#[test]
+fn next_period_waits_for_the_refill() {
+	new_test_ext().execute_with(|| {
+		MaxRefillsPerBlock::set(1);
+		for staker in 1..=3 {
+			assert_ok!(Feeless::stake_bic(Origin::signed(staker), 10_000 * BIC));
+		}
+		new_period();
+		assert_noop!(Feeless::force_period(Origin::root()), Error::<Test>::RefillInProgress);
+
+		System::set_block_number(11);
+		Feeless::on_finalize(11);
+		assert_eq!(Feeless::last_period_block(), 1);
+
+		Feeless::on_initialize(12);
+		Feeless::on_initialize(13);
+		assert!(Feeless::pending_refill().is_none());
+		System::set_block_number(13);
+		Feeless::on_finalize(13);
+		assert_eq!(Feeless::last_period_block(), 13);
 	});
 }
@@ -0,0 +1,654 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Bein social groups.
+//!
+//! A group has an owner, a set of admins, a hash of its off-chain metadata and a list of
+//! members. Admins install extensions into their group: EVM contracts or named pallet
+//! features that act as the group's dapps. Other pallets and precompiles check membership
+//! and admin rights through the `GroupMembership` trait, so a group can gate who may use
+//! its extensions.
+//!
+//! Admins invite accounts, which only become members once they accept. Members, extensions
+//! and pending invitations are capped per group, so that destroying a group stays bounded.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	ensure,
+	pallet_prelude::*,
+	traits::{BalanceStatus, Currency, ReservableCurrency},
+	transactional,
+	weights::Weight,
+	RuntimeDebug,
+};
+use frame_system::{ensure_signed, pallet_prelude::*};
+use scale_info::TypeInfo;
+use sp_core::{H160, H256};
+use sp_runtime::{
+	traits::{StaticLookup, Zero},
+	DispatchResult,
+};
+use sp_std::vec::Vec;
+
+pub type GroupId = u32;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+pub trait WeightInfo {
+	fn create_group() -> Weight;
+	fn set_metadata() -> Weight;
+	fn add_admin() -> Weight;
+	fn remove_admin() -> Weight;
+	fn transfer_ownership() -> Weight;
+	fn install_extension() -> Weight;
+	fn uninstall_extension() -> Weight;
+	fn add_member() -> Weight;
+	fn remove_member() -> Weight;
+	fn leave_group() -> Weight;
+	fn destroy_group(m: u32, e: u32) -> Weight;
+	fn accept_invitation() -> Weight;
+	fn cancel_invitation() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn create_group() -> Weight {
+		0
+	}
+	fn set_metadata() -> Weight {
+		0
+	}
+	fn add_admin() -> Weight {
+		0
+	}
+	fn remove_admin() -> Weight {
+		0
+	}
+	fn transfer_ownership() -> Weight {
+		0
+	}
+	fn install_extension() -> Weight {
+		0
+	}
+	fn uninstall_extension() -> Weight {
+		0
+	}
+	fn add_member() -> Weight {
+		0
+	}
+	fn remove_member() -> Weight {
+		0
+	}
+	fn leave_group() -> Weight {
+		0
+	}
+	fn destroy_group(_m: u32, _e: u32) -> Weight {
+		0
+	}
+	fn accept_invitation() -> Weight {
+		0
+	}
+	fn cancel_invitation() -> Weight {
+		0
+	}
+}
+
+/// Group permissions, for pallets and precompiles that act on behalf of groups.
+pub trait GroupMembership<AccountId> {
+	fn is_member(group: GroupId, who: &AccountId) -> bool;
+
+	/// Whether `who` is the owner or an admin of `group`.
+	fn is_admin(group: GroupId, who: &AccountId) -> bool;
+
+	fn has_extension(group: GroupId, extension: &Extension) -> bool;
+}
+
+/// A dapp installed into a group.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum Extension {
+	/// An EVM contract.
+	Contract(H160),
+	/// A runtime feature, by name.
+	Feature(Vec<u8>),
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct GroupDetails<AccountId, Balance> {
+	pub owner: AccountId,
+	/// Accounts besides the owner that manage members, metadata and extensions.
+	pub admins: Vec<AccountId>,
+	/// Hash of the group metadata stored off chain.
+	pub metadata: H256,
+	pub deposit: Balance,
+	pub members: u32,
+	pub extensions: u32,
+	/// Pending invitations to join.
+	pub invitations: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency used for the group deposit.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Deposit reserved from the owner for the lifetime of a group.
+		#[pallet::constant]
+		type GroupDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum number of admins of a group, not counting the owner.
+		#[pallet::constant]
+		type MaxAdmins: Get<u32>;
+
+		/// Maximum number of members of a group, the owner included.
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// Maximum number of extensions installed into a group.
+		#[pallet::constant]
+		type MaxExtensions: Get<u32>;
+
+		/// Maximum number of pending invitations of a group.
+		#[pallet::constant]
+		type MaxInvitations: Get<u32>;
+
+		/// Maximum length of an extension feature name.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_group_id)]
+	pub type NextGroupId<T: Config> = StorageValue<_, GroupId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn groups)]
+	pub type Groups<T: Config> =
+		StorageMap<_, Twox64Concat, GroupId, GroupDetails<T::AccountId, BalanceOf<T>>>;
+
+	#[pallet::storage]
+	pub type Members<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, GroupId, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	#[pallet::storage]
+	pub type Extensions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, GroupId, Blake2_128Concat, Extension, (), OptionQuery>;
+
+	/// Accounts invited into a group that have not accepted yet.
+	#[pallet::storage]
+	pub type Invitations<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, GroupId, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A group was created. \[group, owner\]
+		GroupCreated(GroupId, T::AccountId),
+		/// The metadata hash of a group changed. \[group, metadata\]
+		MetadataSet(GroupId, H256),
+		/// An admin was added. \[group, admin\]
+		AdminAdded(GroupId, T::AccountId),
+		/// An admin was removed. \[group, admin\]
+		AdminRemoved(GroupId, T::AccountId),
+		/// A group changed hands. \[group, from, to\]
+		OwnershipTransferred(GroupId, T::AccountId, T::AccountId),
+		/// An extension was installed into a group. \[group, extension\]
+		ExtensionInstalled(GroupId, Extension),
+		/// An extension was removed from a group. \[group, extension\]
+		ExtensionUninstalled(GroupId, Extension),
+		/// An account was invited into a group. \[group, account\]
+		MemberInvited(GroupId, T::AccountId),
+		/// An invitation was declined or withdrawn. \[group, account\]
+		InvitationCancelled(GroupId, T::AccountId),
+		/// An account accepted an invitation and joined a group. \[group, member\]
+		MemberAdded(GroupId, T::AccountId),
+		/// An account left or was removed from a group. \[group, member\]
+		MemberRemoved(GroupId, T::AccountId),
+		/// A group was destroyed. \[group\]
+		GroupDestroyed(GroupId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Group does not exist
+		UnknownGroup,
+		/// Origin may not perform this operation
+		NoPermission,
+		/// Account is already an admin
+		AlreadyAdmin,
+		/// Account is not an admin
+		NotAdmin,
+		/// Group has `MaxAdmins` admins
+		TooManyAdmins,
+		/// Account is already a member
+		AlreadyMember,
+		/// Account is not a member
+		NotMember,
+		/// Extension is already installed
+		AlreadyInstalled,
+		/// Extension is not installed
+		NotInstalled,
+		/// Feature name is longer than `StringLimit`
+		BadExtension,
+		/// Group has `MaxMembers` members
+		TooManyMembers,
+		/// Group has `MaxExtensions` extensions
+		TooManyExtensions,
+		/// Group has `MaxInvitations` pending invitations
+		TooManyInvitations,
+		/// Account is already invited
+		AlreadyInvited,
+		/// Account is not invited
+		NotInvited,
+		/// The owner cannot leave or be removed from its group
+		IsOwner,
+		/// No group ids left
+		NoAvailableGroupId,
+		/// The owner's reserved balance no longer covers the group deposit
+		DepositNotReserved,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a group owned by the origin, who becomes its first member.
+		#[pallet::weight(T::WeightInfo::create_group())]
+		pub fn create_group(origin: OriginFor<T>, metadata: H256) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let id = Self::next_group_id();
+			let next_id = id.checked_add(1).ok_or(Error::<T>::NoAvailableGroupId)?;
+
+			let deposit = T::GroupDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+
+			NextGroupId::<T>::put(next_id);
+			Groups::<T>::insert(
+				id,
+				GroupDetails {
+					owner: who.clone(),
+					admins: Vec::new(),
+					metadata,
+					deposit,
+					members: 1,
+					extensions: 0,
+					invitations: 0,
+				},
+			);
+			Members::<T>::insert(id, &who, ());
+			Self::deposit_event(Event::GroupCreated(id, who));
+
+			Ok(().into())
+		}
+
+		/// Change the metadata hash of a group. Admins only.
+		#[pallet::weight(T::WeightInfo::set_metadata())]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+			metadata: H256,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Groups::<T>::try_mutate(group, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownGroup)?;
+				ensure!(Self::can_manage(details, &who), Error::<T>::NoPermission);
+				details.metadata = metadata;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::MetadataSet(group, metadata));
+
+			Ok(().into())
+		}
+
+		/// Make a member of a group one of its admins. Owner only.
+		#[pallet::weight(T::WeightInfo::add_admin())]
+		pub fn add_admin(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+			admin: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+
+			Groups::<T>::try_mutate(group, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownGroup)?;
+				ensure!(details.owner == who, Error::<T>::NoPermission);
+				ensure!(Members::<T>::contains_key(group, &admin), Error::<T>::NotMember);
+				ensure!(
+					details.owner != admin && !details.admins.contains(&admin),
+					Error::<T>::AlreadyAdmin
+				);
+				ensure!(
+					(details.admins.len() as u32) < T::MaxAdmins::get(),
+					Error::<T>::TooManyAdmins
+				);
+				details.admins.push(admin.clone());
+				Ok(())
+			})?;
+			Self::deposit_event(Event::AdminAdded(group, admin));
+
+			Ok(().into())
+		}
+
+		/// Take admin rights away from an account. Owner only.
+		#[pallet::weight(T::WeightInfo::remove_admin())]
+		pub fn remove_admin(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+			admin: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+
+			Groups::<T>::try_mutate(group, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownGroup)?;
+				ensure!(details.owner == who, Error::<T>::NoPermission);
+				ensure!(Self::remove_admin_from(details, &admin), Error::<T>::NotAdmin);
+				Ok(())
+			})?;
+			Self::deposit_event(Event::AdminRemoved(group, admin));
+
+			Ok(().into())
+		}
+
+		/// Hand a group, and its deposit, over to one of its members. Owner only.
+		#[pallet::weight(T::WeightInfo::transfer_ownership())]
+		#[transactional]
+		pub fn transfer_ownership(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+			new_owner: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let new_owner = T::Lookup::lookup(new_owner)?;
+
+			Groups::<T>::try_mutate(group, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownGroup)?;
+				ensure!(details.owner == who, Error::<T>::NoPermission);
+				ensure!(Members::<T>::contains_key(group, &new_owner), Error::<T>::NotMember);
+
+				let not_moved = T::Currency::repatriate_reserved(
+					&who,
+					&new_owner,
+					details.deposit,
+					BalanceStatus::Reserved,
+				)?;
+				ensure!(not_moved.is_zero(), Error::<T>::DepositNotReserved);
+				Self::remove_admin_from(details, &new_owner);
+				details.owner = new_owner.clone();
+				Ok(())
+			})?;
+			Self::deposit_event(Event::OwnershipTransferred(group, who, new_owner));
+
+			Ok(().into())
+		}
+
+		/// Install an extension into a group. Admins only.
+		#[pallet::weight(T::WeightInfo::install_extension())]
+		pub fn install_extension(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+			extension: Extension,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			if let Extension::Feature(name) = &extension {
+				ensure!(name.len() <= T::StringLimit::get() as usize, Error::<T>::BadExtension);
+			}
+			Groups::<T>::try_mutate(group, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownGroup)?;
+				ensure!(Self::can_manage(details, &who), Error::<T>::NoPermission);
+				ensure!(
+					!Extensions::<T>::contains_key(group, &extension),
+					Error::<T>::AlreadyInstalled
+				);
+				ensure!(
+					details.extensions < T::MaxExtensions::get(),
+					Error::<T>::TooManyExtensions
+				);
+				details.extensions += 1;
+				Ok(())
+			})?;
+
+			Extensions::<T>::insert(group, &extension, ());
+			Self::deposit_event(Event::ExtensionInstalled(group, extension));
+
+			Ok(().into())
+		}
+
+		/// Remove an extension from a group. Admins only.
+		#[pallet::weight(T::WeightInfo::uninstall_extension())]
+		pub fn uninstall_extension(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+			extension: Extension,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Groups::<T>::try_mutate(group, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownGroup)?;
+				ensure!(Self::can_manage(details, &who), Error::<T>::NoPermission);
+				ensure!(
+					Extensions::<T>::contains_key(group, &extension),
+					Error::<T>::NotInstalled
+				);
+				details.extensions = details.extensions.saturating_sub(1);
+				Ok(())
+			})?;
+
+			Extensions::<T>::remove(group, &extension);
+			Self::deposit_event(Event::ExtensionUninstalled(group, extension));
+
+			Ok(().into())
+		}
+
+		/// Invite an account into a group. It only becomes a member once it accepts with
+		/// `accept_invitation`. Admins only.
+		#[pallet::weight(T::WeightInfo::add_member())]
+		pub fn add_member(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+			member: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let member = T::Lookup::lookup(member)?;
+
+			Groups::<T>::try_mutate(group, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownGroup)?;
+				ensure!(Self::can_manage(details, &who), Error::<T>::NoPermission);
+				ensure!(!Members::<T>::contains_key(group, &member), Error::<T>::AlreadyMember);
+				ensure!(
+					!Invitations::<T>::contains_key(group, &member),
+					Error::<T>::AlreadyInvited
+				);
+				ensure!(
+					details.invitations < T::MaxInvitations::get(),
+					Error::<T>::TooManyInvitations
+				);
+				details.invitations += 1;
+				Ok(())
+			})?;
+			Invitations::<T>::insert(group, &member, ());
+			Self::deposit_event(Event::MemberInvited(group, member));
+
+			Ok(().into())
+		}
+
+		/// Remove an account, and its admin rights, from a group. Admins only.
+		#[pallet::weight(T::WeightInfo::remove_member())]
+		pub fn remove_member(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+			member: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let member = T::Lookup::lookup(member)?;
+
+			let details = Self::groups(group).ok_or(Error::<T>::UnknownGroup)?;
+			ensure!(Self::can_manage(&details, &who), Error::<T>::NoPermission);
+			// Only the owner may remove another admin.
+			ensure!(
+				details.owner == who || !details.admins.contains(&member),
+				Error::<T>::NoPermission
+			);
+			Self::do_remove_member(group, &member)?;
+
+			Ok(().into())
+		}
+
+		/// Leave a group the origin is a member of.
+		#[pallet::weight(T::WeightInfo::leave_group())]
+		pub fn leave_group(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Self::do_remove_member(group, &who)?;
+
+			Ok(().into())
+		}
+
+		/// Destroy a group with all its members, invitations and extensions, returning the
+		/// deposit. Owner only.
+		#[pallet::weight(T::WeightInfo::destroy_group(
+			T::MaxMembers::get().saturating_add(T::MaxInvitations::get()),
+			T::MaxExtensions::get(),
+		))]
+		pub fn destroy_group(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let details = Self::groups(group).ok_or(Error::<T>::UnknownGroup)?;
+			ensure!(details.owner == who, Error::<T>::NoPermission);
+
+			T::Currency::unreserve(&who, details.deposit);
+			Groups::<T>::remove(group);
+			// All bounded by the caps on members, invitations and extensions.
+			Members::<T>::remove_prefix(group, None);
+			Invitations::<T>::remove_prefix(group, None);
+			Extensions::<T>::remove_prefix(group, None);
+			Self::deposit_event(Event::GroupDestroyed(group));
+
+			let accounts = details.members.saturating_add(details.invitations);
+			Ok(Some(T::WeightInfo::destroy_group(accounts, details.extensions)).into())
+		}
+
+		/// Accept an invitation into a group, becoming one of its members.
+		#[pallet::weight(T::WeightInfo::accept_invitation())]
+		pub fn accept_invitation(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Groups::<T>::try_mutate(group, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownGroup)?;
+				ensure!(Invitations::<T>::contains_key(group, &who), Error::<T>::NotInvited);
+				ensure!(details.members < T::MaxMembers::get(), Error::<T>::TooManyMembers);
+				details.invitations = details.invitations.saturating_sub(1);
+				details.members += 1;
+				Ok(())
+			})?;
+			Invitations::<T>::remove(group, &who);
+			Members::<T>::insert(group, &who, ());
+			Self::deposit_event(Event::MemberAdded(group, who));
+
+			Ok(().into())
+		}
+
+		/// Decline an invitation of the origin, or withdraw one as an admin of the group.
+		#[pallet::weight(T::WeightInfo::cancel_invitation())]
+		pub fn cancel_invitation(
+			origin: OriginFor<T>,
+			#[pallet::compact] group: GroupId,
+			account: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let account = T::Lookup::lookup(account)?;
+
+			Groups::<T>::try_mutate(group, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownGroup)?;
+				ensure!(
+					who == account || Self::can_manage(details, &who),
+					Error::<T>::NoPermission
+				);
+				ensure!(Invitations::<T>::contains_key(group, &account), Error::<T>::NotInvited);
+				details.invitations = details.invitations.saturating_sub(1);
+				Ok(())
+			})?;
+			Invitations::<T>::remove(group, &account);
+			Self::deposit_event(Event::InvitationCancelled(group, account));
+
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn can_manage(details: &GroupDetails<T::AccountId, BalanceOf<T>>, who: &T::AccountId) -> bool {
+		&details.owner == who || details.admins.contains(who)
+	}
+
+	/// Drop `who` from the admins, returning whether it was one.
+	fn remove_admin_from(
+		details: &mut GroupDetails<T::AccountId, BalanceOf<T>>,
+		who: &T::AccountId,
+	) -> bool {
+		let admins = details.admins.len();
+		details.admins.retain(|admin| admin != who);
+		details.admins.len() != admins
+	}
+
+	fn do_remove_member(group: GroupId, member: &T::AccountId) -> DispatchResult {
+		Groups::<T>::try_mutate(group, |maybe_details| -> DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownGroup)?;
+			ensure!(&details.owner != member, Error::<T>::IsOwner);
+			ensure!(Members::<T>::contains_key(group, member), Error::<T>::NotMember);
+			Self::remove_admin_from(details, member);
+			details.members = details.members.saturating_sub(1);
+			Ok(())
+		})?;
+		Members::<T>::remove(group, member);
+		Self::deposit_event(Event::MemberRemoved(group, member.clone()));
+		Ok(())
+	}
+}
+
+impl<T: Config> GroupMembership<T::AccountId> for Pallet<T> {
+	fn is_member(group: GroupId, who: &T::AccountId) -> bool {
+		Members::<T>::contains_key(group, who)
+	}
+
+	fn is_admin(group: GroupId, who: &T::AccountId) -> bool {
+		Self::groups(group).map_or(false, |details| Self::can_manage(&details, who))
+	}
+
+	fn has_extension(group: GroupId, extension: &Extension) -> bool {
+		Extensions::<T>::contains_key(group, extension)
+	}
+}
@@ -0,0 +1,231 @@
+use crate::{mock::*, Error, Event as GroupsEvent, Extension, GroupMembership};
+use frame_support::{assert_noop, assert_ok, traits::ReservableCurrency};
+use sp_core::{H160, H256};
+
+/// Account 1 creates group 0.
+fn create_group() {
+	assert_ok!(Groups::create_group(Origin::signed(1), H256::repeat_byte(1)));
+}
+
+/// Account 1 invites `who` into group 0, and `who` accepts.
+fn join(who: u64) {
+	assert_ok!(Groups::add_member(Origin::signed(1), 0, who));
+	assert_ok!(Groups::accept_invitation(Origin::signed(who), 0));
+}
+
+fn is_member(who: u64) -> bool {
+	<Groups as GroupMembership<u64>>::is_member(0, &who)
+}
+
+fn is_admin(who: u64) -> bool {
+	<Groups as GroupMembership<u64>>::is_admin(0, &who)
+}
+
+fn contract() -> Extension {
+	Extension::Contract(H160::repeat_byte(1))
+}
+
+#[test]
+fn creator_owns_the_group_and_is_its_first_member() {
+	new_test_ext().execute_with(|| {
+		create_group();
+
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert!(is_member(1));
+		assert!(is_admin(1));
+		let details = Groups::groups(0).unwrap();
+		assert_eq!(details.metadata, H256::repeat_byte(1));
+		assert_eq!(details.members, 1);
+		System::assert_last_event(Event::Groups(GroupsEvent::GroupCreated(0, 1)));
+	});
+}
+
+#[test]
+fn invited_account_only_joins_once_it_accepts() {
+	new_test_ext().execute_with(|| {
+		create_group();
+		assert_noop!(Groups::add_member(Origin::signed(2), 0, 3), Error::<Test>::NoPermission);
+		assert_ok!(Groups::add_member(Origin::signed(1), 0, 2));
+		assert!(!is_member(2));
+		assert_eq!(Groups::groups(0).unwrap().invitations, 1);
+		assert_noop!(Groups::add_member(Origin::signed(1), 0, 2), Error::<Test>::AlreadyInvited);
+		assert_noop!(Groups::accept_invitation(Origin::signed(3), 0), Error::<Test>::NotInvited);
+
+		assert_ok!(Groups::accept_invitation(Origin::signed(2), 0));
+		assert!(is_member(2));
+		let details = Groups::groups(0).unwrap();
+		assert_eq!((details.members, details.invitations), (2, 0));
+		System::assert_last_event(Event::Groups(GroupsEvent::MemberAdded(0, 2)));
+		assert_noop!(Groups::add_member(Origin::signed(1), 0, 2), Error::<Test>::AlreadyMember);
+	});
+}
+
+#[test]
+fn invitation_is_cancelled_by_the_invitee_or_an_admin() {
+	new_test_ext().execute_with(|| {
+		create_group();
+		assert_ok!(Groups::add_member(Origin::signed(1), 0, 2));
+		assert_ok!(Groups::add_member(Origin::signed(1), 0, 3));
+		assert_noop!(
+			Groups::cancel_invitation(Origin::signed(4), 0, 2),
+			Error::<Test>::NoPermission
+		);
+
+		assert_ok!(Groups::cancel_invitation(Origin::signed(2), 0, 2));
+		assert_ok!(Groups::cancel_invitation(Origin::signed(1), 0, 3));
+		assert_eq!(Groups::groups(0).unwrap().invitations, 0);
+		System::assert_last_event(Event::Groups(GroupsEvent::InvitationCancelled(0, 3)));
+		assert_noop!(
+			Groups::cancel_invitation(Origin::signed(2), 0, 2),
+			Error::<Test>::NotInvited
+		);
+	});
+}
+
+#[test]
+fn invitations_and_members_are_capped() {
+	new_test_ext().execute_with(|| {
+		create_group();
+		assert_ok!(Groups::add_member(Origin::signed(1), 0, 2));
+		assert_ok!(Groups::add_member(Origin::signed(1), 0, 3));
+		assert_noop!(
+			Groups::add_member(Origin::signed(1), 0, 4),
+			Error::<Test>::TooManyInvitations
+		);
+
+		assert_ok!(Groups::accept_invitation(Origin::signed(2), 0));
+		assert_ok!(Groups::accept_invitation(Origin::signed(3), 0));
+		assert_ok!(Groups::add_member(Origin::signed(1), 0, 4));
+		assert_noop!(
+			Groups::accept_invitation(Origin::signed(4), 0),
+			Error::<Test>::TooManyMembers
+		);
+	});
+}
+
+#[test]
+fn owner_manages_the_admins() {
+	new_test_ext().execute_with(|| {
+		create_group();
+		join(2);
+		join(3);
+		assert_noop!(Groups::add_admin(Origin::signed(1), 0, 4), Error::<Test>::NotMember);
+		assert_noop!(Groups::add_admin(Origin::signed(2), 0, 3), Error::<Test>::NoPermission);
+		assert_noop!(Groups::add_admin(Origin::signed(1), 0, 1), Error::<Test>::AlreadyAdmin);
+
+		assert_ok!(Groups::add_admin(Origin::signed(1), 0, 2));
+		assert!(is_admin(2));
+		assert_noop!(Groups::add_admin(Origin::signed(1), 0, 3), Error::<Test>::TooManyAdmins);
+		// Admins manage members, but not the owner.
+		assert_ok!(Groups::remove_member(Origin::signed(2), 0, 3));
+		assert_noop!(Groups::remove_member(Origin::signed(2), 0, 1), Error::<Test>::IsOwner);
+
+		assert_ok!(Groups::remove_admin(Origin::signed(1), 0, 2));
+		assert!(!is_admin(2));
+		assert_noop!(Groups::remove_admin(Origin::signed(1), 0, 2), Error::<Test>::NotAdmin);
+	});
+}
+
+#[test]
+fn ownership_moves_with_the_deposit() {
+	new_test_ext().execute_with(|| {
+		create_group();
+		assert_noop!(
+			Groups::transfer_ownership(Origin::signed(1), 0, 2),
+			Error::<Test>::NotMember
+		);
+		join(2);
+
+		assert_ok!(Groups::transfer_ownership(Origin::signed(1), 0, 2));
+		assert_eq!(Groups::groups(0).unwrap().owner, 2);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert!(!is_admin(1));
+		System::assert_last_event(Event::Groups(GroupsEvent::OwnershipTransferred(0, 1, 2)));
+	});
+}
+
+#[test]
+fn ownership_stays_unless_the_whole_deposit_moves() {
+	new_test_ext().execute_with(|| {
+		create_group();
+		join(2);
+		Balances::unreserve(&1, 4);
+
+		assert_noop!(
+			Groups::transfer_ownership(Origin::signed(1), 0, 2),
+			Error::<Test>::DepositNotReserved
+		);
+		assert_eq!(Groups::groups(0).unwrap().owner, 1);
+		assert_eq!(Balances::reserved_balance(1), 6);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn admins_install_a_limited_number_of_extensions() {
+	new_test_ext().execute_with(|| {
+		create_group();
+		assert_noop!(
+			Groups::install_extension(Origin::signed(2), 0, contract()),
+			Error::<Test>::NoPermission
+		);
+		let long_name = Extension::Feature(b"too long!".to_vec());
+		assert_noop!(
+			Groups::install_extension(Origin::signed(1), 0, long_name),
+			Error::<Test>::BadExtension
+		);
+
+		assert_ok!(Groups::install_extension(Origin::signed(1), 0, contract()));
+		assert!(<Groups as GroupMembership<u64>>::has_extension(0, &contract()));
+		assert_noop!(
+			Groups::install_extension(Origin::signed(1), 0, contract()),
+			Error::<Test>::AlreadyInstalled
+		);
+		assert_noop!(
+			Groups::install_extension(Origin::signed(1), 0, Extension::Feature(b"tips".to_vec())),
+			Error::<Test>::TooManyExtensions
+		);
+
+		assert_ok!(Groups::uninstall_extension(Origin::signed(1), 0, contract()));
+		assert!(!<Groups as GroupMembership<u64>>::has_extension(0, &contract()));
+		assert_noop!(
+			Groups::uninstall_extension(Origin::signed(1), 0, contract()),
+			Error::<Test>::NotInstalled
+		);
+	});
+}
+
+#[test]
+fn members_but_not_the_owner_can_leave() {
+	new_test_ext().execute_with(|| {
+		create_group();
+		join(2);
+		assert_noop!(Groups::leave_group(Origin::signed(1), 0), Error::<Test>::IsOwner);
+		assert_noop!(Groups::leave_group(Origin::signed(3), 0), Error::<Test>::NotMember);
+
+		assert_ok!(Groups::leave_group(Origin::signed(2), 0));
+		assert!(!is_member(2));
+		assert_eq!(Groups::groups(0).unwrap().members, 1);
+		System::assert_last_event(Event::Groups(GroupsEvent::MemberRemoved(0, 2)));
+	});
+}
+
+#[test]
+fn destroying_a_group_clears_it_and_returns_the_deposit() {
+	new_test_ext().execute_with(|| {
+		create_group();
+		join(2);
+		assert_ok!(Groups::add_member(Origin::signed(1), 0, 3));
+		assert_ok!(Groups::install_extension(Origin::signed(1), 0, contract()));
+		assert_noop!(Groups::destroy_group(Origin::signed(2), 0), Error::<Test>::NoPermission);
+
+		assert_ok!(Groups::destroy_group(Origin::signed(1), 0));
+		assert!(Groups::groups(0).is_none());
+		assert!(!is_member(2));
+		assert!(!<Groups as GroupMembership<u64>>::has_extension(0, &contract()));
+		assert_noop!(Groups::accept_invitation(Origin::signed(3), 0), Error::<Test>::UnknownGroup);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_last_event(Event::Groups(GroupsEvent::GroupDestroyed(0)));
+	});
+}
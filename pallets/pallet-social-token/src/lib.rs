@@ -0,0 +1,522 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Creator-issued social tokens.
+//!
+//! Any account can create a token by reserving `TokenDeposit`. The creator picks a name,
+//! symbol, decimals, a hard cap on supply and an issuance policy: `Fixed` tokens mint their
+//! whole initial supply at creation, `Mintable` tokens let the creator mint more later, up
+//! to the cap. Every token is reachable from the EVM at `token_address(id)`, where the
+//! runtime serves it as an ERC-20 precompile.
+//!
+//! Once its whole supply is burned, the owner can `destroy` a token. It is then frozen and
+//! anyone can clear its balances and allowances with `destroy_accounts`, at most
+//! `RemoveItemsLimit` per call. The last call removes the token and returns the deposit.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	ensure,
+	pallet_prelude::*,
+	traits::{Currency, ReservableCurrency},
+	transactional,
+	weights::Weight,
+	RuntimeDebug,
+};
+use frame_system::{ensure_signed, pallet_prelude::*};
+use scale_info::TypeInfo;
+use sp_core::H160;
+use sp_runtime::{
+	traits::{CheckedAdd, CheckedSub, Saturating, StaticLookup, Zero},
+	DispatchResult,
+};
+use sp_std::vec::Vec;
+
+pub type TokenId = u32;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Prefix of the EVM address of every social token, followed by the big-endian token id.
+pub const TOKEN_ADDRESS_PREFIX: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+
+/// Code stored at token addresses so that Solidity's `extcodesize` checks pass. It reverts
+/// if ever executed, which cannot happen since the precompile takes precedence.
+pub const TOKEN_ADDRESS_CODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
+
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn mint() -> Weight;
+	fn burn() -> Weight;
+	fn transfer() -> Weight;
+	fn approve() -> Weight;
+	fn transfer_from() -> Weight;
+	fn destroy() -> Weight;
+	fn destroy_accounts(n: u32) -> Weight;
+}
+
+impl WeightInfo for () {
+	fn create() -> Weight {
+		0
+	}
+	fn mint() -> Weight {
+		0
+	}
+	fn burn() -> Weight {
+		0
+	}
+	fn transfer() -> Weight {
+		0
+	}
+	fn approve() -> Weight {
+		0
+	}
+	fn transfer_from() -> Weight {
+		0
+	}
+	fn destroy() -> Weight {
+		0
+	}
+	fn destroy_accounts(_n: u32) -> Weight {
+		0
+	}
+}
+
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum IssuancePolicy {
+	/// The whole supply is minted at creation.
+	Fixed,
+	/// The owner may mint more tokens, up to the cap.
+	Mintable,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct TokenDetails<AccountId, Balance> {
+	pub owner: AccountId,
+	pub supply: Balance,
+	pub cap: Balance,
+	pub issuance: IssuancePolicy,
+	pub deposit: Balance,
+	/// Whether the token is being destroyed. Its balances can no longer change.
+	pub destroying: bool,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+pub struct TokenMetadata {
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,
+	pub decimals: u8,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_evm::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency used for the creation deposit.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Deposit reserved from the creator for the lifetime of a token.
+		#[pallet::constant]
+		type TokenDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum length of a token name or symbol.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// Most balances and allowances removed by one `destroy_accounts` call.
+		#[pallet::constant]
+		type RemoveItemsLimit: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_token_id)]
+	pub type NextTokenId<T: Config> = StorageValue<_, TokenId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tokens)]
+	pub type Tokens<T: Config> =
+		StorageMap<_, Twox64Concat, TokenId, TokenDetails<T::AccountId, BalanceOf<T>>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn metadata)]
+	pub type Metadata<T: Config> = StorageMap<_, Twox64Concat, TokenId, TokenMetadata, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn balance_of)]
+	pub type TokenBalances<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		TokenId,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Amount `spender` may transfer on behalf of `owner`, keyed by `(owner, spender)`.
+	#[pallet::storage]
+	#[pallet::getter(fn allowance)]
+	pub type Allowances<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		TokenId,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId),
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A token was created. \[token_id, owner, cap\]
+		Created(TokenId, T::AccountId, BalanceOf<T>),
+		/// Tokens were minted. \[token_id, beneficiary, amount\]
+		Issued(TokenId, T::AccountId, BalanceOf<T>),
+		/// Tokens were burned. \[token_id, owner, amount\]
+		Burned(TokenId, T::AccountId, BalanceOf<T>),
+		/// Tokens were transferred. \[token_id, from, to, amount\]
+		Transferred(TokenId, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// An allowance was set. \[token_id, owner, spender, amount\]
+		Approved(TokenId, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// The owner started destroying a token with no supply left. \[token_id\]
+		DestructionStarted(TokenId),
+		/// A token was destroyed and its deposit returned. \[token_id\]
+		Destroyed(TokenId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Token does not exist
+		UnknownToken,
+		/// Origin is not the token owner
+		NoPermission,
+		/// Minting would exceed the token cap
+		CapExceeded,
+		/// Token has a fixed supply
+		NotMintable,
+		/// Account balance too low
+		InsufficientBalance,
+		/// Allowance too low
+		InsufficientAllowance,
+		/// Name or symbol is empty or longer than `StringLimit`
+		BadMetadata,
+		/// Token still has supply
+		InUse,
+		/// Token is being destroyed
+		Destroying,
+		/// Token is not being destroyed
+		NotDestroying,
+		/// No token ids left
+		NoAvailableTokenId,
+		/// Arithmetic overflow
+		Overflow,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a new token owned by the origin, minting `initial_supply` to it.
+		#[pallet::weight(<T as Config>::WeightInfo::create())]
+		#[transactional]
+		pub fn create(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			cap: BalanceOf<T>,
+			initial_supply: BalanceOf<T>,
+			issuance: IssuancePolicy,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let limit = T::StringLimit::get() as usize;
+			ensure!(
+				!name.is_empty() && name.len() <= limit && !symbol.is_empty() && symbol.len() <= limit,
+				Error::<T>::BadMetadata
+			);
+			ensure!(initial_supply <= cap, Error::<T>::CapExceeded);
+
+			let id = Self::next_token_id();
+			let next_id = id.checked_add(1).ok_or(Error::<T>::NoAvailableTokenId)?;
+
+			let deposit = T::TokenDeposit::get();
+			<T as Config>::Currency::reserve(&who, deposit)?;
+
+			NextTokenId::<T>::put(next_id);
+			Tokens::<T>::insert(
+				id,
+				TokenDetails {
+					owner: who.clone(),
+					supply: Zero::zero(),
+					cap,
+					issuance,
+					deposit,
+					destroying: false,
+				},
+			);
+			Metadata::<T>::insert(id, TokenMetadata { name, symbol, decimals });
+			pallet_evm::AccountCodes::<T>::insert(Self::token_address(id), TOKEN_ADDRESS_CODE.to_vec());
+			Self::deposit_event(Event::Created(id, who.clone(), cap));
+
+			if !initial_supply.is_zero() {
+				Self::do_mint(id, &who, initial_supply)?;
+			}
+
+			Ok(().into())
+		}
+
+		/// Mint more of a `Mintable` token. Only the token owner can mint.
+		#[pallet::weight(<T as Config>::WeightInfo::mint())]
+		pub fn mint(
+			origin: OriginFor<T>,
+			#[pallet::compact] id: TokenId,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			let details = Self::tokens(id).ok_or(Error::<T>::UnknownToken)?;
+			ensure!(details.owner == who, Error::<T>::NoPermission);
+			ensure!(details.issuance == IssuancePolicy::Mintable, Error::<T>::NotMintable);
+
+			Self::do_mint(id, &beneficiary, amount)?;
+
+			Ok(().into())
+		}
+
+		/// Burn tokens held by the origin.
+		#[pallet::weight(<T as Config>::WeightInfo::burn())]
+		pub fn burn(
+			origin: OriginFor<T>,
+			#[pallet::compact] id: TokenId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Tokens::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownToken)?;
+				TokenBalances::<T>::try_mutate(id, &who, |balance| -> DispatchResult {
+					*balance = balance.checked_sub(&amount).ok_or(Error::<T>::InsufficientBalance)?;
+					Ok(())
+				})?;
+				details.supply = details.supply.saturating_sub(amount);
+				Ok(())
+			})?;
+			Self::deposit_event(Event::Burned(id, who, amount));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::transfer())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			#[pallet::compact] id: TokenId,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			Self::do_transfer(id, &who, &dest, amount)?;
+
+			Ok(().into())
+		}
+
+		/// Allow `spender` to transfer up to `amount` of the origin's tokens.
+		#[pallet::weight(<T as Config>::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			#[pallet::compact] id: TokenId,
+			spender: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let spender = T::Lookup::lookup(spender)?;
+
+			Self::do_approve(id, &who, &spender, amount)?;
+
+			Ok(().into())
+		}
+
+		/// Transfer tokens of `owner` using an allowance granted to the origin.
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_from())]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			#[pallet::compact] id: TokenId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			Self::do_transfer_from(id, &who, &owner, &dest, amount)?;
+
+			Ok(().into())
+		}
+
+		/// Start destroying a token whose whole supply has been burned. Its balances and
+		/// allowances are then removed with `destroy_accounts`.
+		#[pallet::weight(<T as Config>::WeightInfo::destroy())]
+		pub fn destroy(
+			origin: OriginFor<T>,
+			#[pallet::compact] id: TokenId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Tokens::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownToken)?;
+				ensure!(details.owner == who, Error::<T>::NoPermission);
+				ensure!(details.supply.is_zero(), Error::<T>::InUse);
+				ensure!(!details.destroying, Error::<T>::Destroying);
+				details.destroying = true;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::DestructionStarted(id));
+
+			Ok(().into())
+		}
+
+		/// Remove up to `RemoveItemsLimit` balances and allowances of a token being destroyed.
+		/// Once none are left, remove the token and return the deposit to its owner. Anyone
+		/// may call this.
+		#[pallet::weight(<T as Config>::WeightInfo::destroy_accounts(T::RemoveItemsLimit::get()))]
+		pub fn destroy_accounts(
+			origin: OriginFor<T>,
+			#[pallet::compact] id: TokenId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let details = Self::tokens(id).ok_or(Error::<T>::UnknownToken)?;
+			ensure!(details.destroying, Error::<T>::NotDestroying);
+
+			let limit = T::RemoveItemsLimit::get();
+			let mut removed = TokenBalances::<T>::drain_prefix(id).take(limit as usize).count();
+			removed += Allowances::<T>::drain_prefix(id).take(limit as usize - removed).count();
+			let removed = removed as u32;
+
+			if removed < limit {
+				<T as Config>::Currency::unreserve(&details.owner, details.deposit);
+				Tokens::<T>::remove(id);
+				Metadata::<T>::remove(id);
+				pallet_evm::AccountCodes::<T>::remove(Self::token_address(id));
+				Self::deposit_event(Event::Destroyed(id));
+			}
+
+			Ok(Some(<T as Config>::WeightInfo::destroy_accounts(removed)).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// EVM address at which token `id` is served as an ERC-20 precompile.
+	pub fn token_address(id: TokenId) -> H160 {
+		let mut address = [0u8; 20];
+		address[0..4].copy_from_slice(&TOKEN_ADDRESS_PREFIX);
+		address[16..20].copy_from_slice(&id.to_be_bytes());
+		H160::from(address)
+	}
+
+	/// Token id served at `address`, if it has the token address layout.
+	pub fn token_id(address: H160) -> Option<TokenId> {
+		let bytes = address.as_bytes();
+		if bytes[0..4] != TOKEN_ADDRESS_PREFIX || bytes[4..16].iter().any(|b| *b != 0) {
+			return None;
+		}
+		let mut id = [0u8; 4];
+		id.copy_from_slice(&bytes[16..20]);
+		Some(TokenId::from_be_bytes(id))
+	}
+
+	pub fn total_supply(id: TokenId) -> BalanceOf<T> {
+		Self::tokens(id).map(|details| details.supply).unwrap_or_else(Zero::zero)
+	}
+
+	/// Ensure token `id` exists and is not being destroyed.
+	fn ensure_live(id: TokenId) -> DispatchResult {
+		let details = Self::tokens(id).ok_or(Error::<T>::UnknownToken)?;
+		ensure!(!details.destroying, Error::<T>::Destroying);
+		Ok(())
+	}
+
+	fn do_mint(id: TokenId, beneficiary: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		Tokens::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownToken)?;
+			ensure!(!details.destroying, Error::<T>::Destroying);
+			let supply = details.supply.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+			ensure!(supply <= details.cap, Error::<T>::CapExceeded);
+			details.supply = supply;
+			Ok(())
+		})?;
+		TokenBalances::<T>::mutate(id, beneficiary, |balance| *balance = balance.saturating_add(amount));
+		Self::deposit_event(Event::Issued(id, beneficiary.clone(), amount));
+		Ok(())
+	}
+
+	pub fn do_transfer(
+		id: TokenId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		Self::ensure_live(id)?;
+		if from != to {
+			TokenBalances::<T>::try_mutate(id, from, |balance| -> DispatchResult {
+				*balance = balance.checked_sub(&amount).ok_or(Error::<T>::InsufficientBalance)?;
+				Ok(())
+			})?;
+			TokenBalances::<T>::mutate(id, to, |balance| *balance = balance.saturating_add(amount));
+		}
+		Self::deposit_event(Event::Transferred(id, from.clone(), to.clone(), amount));
+		Ok(())
+	}
+
+	pub fn do_approve(
+		id: TokenId,
+		owner: &T::AccountId,
+		spender: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		Self::ensure_live(id)?;
+		Allowances::<T>::insert(id, (owner, spender), amount);
+		Self::deposit_event(Event::Approved(id, owner.clone(), spender.clone(), amount));
+		Ok(())
+	}
+
+	#[transactional]
+	pub fn do_transfer_from(
+		id: TokenId,
+		spender: &T::AccountId,
+		owner: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		Allowances::<T>::try_mutate(id, (owner, spender), |allowance| -> DispatchResult {
+			*allowance = allowance.checked_sub(&amount).ok_or(Error::<T>::InsufficientAllowance)?;
+			Ok(())
+		})?;
+		Self::do_transfer(id, owner, to, amount)
+	}
+}
@@ -0,0 +1,178 @@
+use crate::{mock::*, Error, Event as SocialTokenEvent, IssuancePolicy, TOKEN_ADDRESS_CODE};
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H160;
+
+/// Account 1 creates token 0 with a cap of 1_000, holding `initial_supply` of it.
+fn create(initial_supply: u64, issuance: IssuancePolicy) {
+	assert_ok!(SocialToken::create(
+		Origin::signed(1),
+		b"Bein".to_vec(),
+		b"BIN".to_vec(),
+		18,
+		1_000,
+		initial_supply,
+		issuance
+	));
+}
+
+#[test]
+fn creating_a_token_reserves_the_deposit_and_mints_the_initial_supply() {
+	new_test_ext().execute_with(|| {
+		create(100, IssuancePolicy::Fixed);
+
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(SocialToken::balance_of(0, 1), 100);
+		assert_eq!(SocialToken::total_supply(0), 100);
+		assert_eq!(SocialToken::metadata(0).symbol, b"BIN".to_vec());
+		assert_eq!(SocialToken::next_token_id(), 1);
+		assert_eq!(
+			pallet_evm::AccountCodes::<Test>::get(SocialToken::token_address(0)),
+			TOKEN_ADDRESS_CODE.to_vec()
+		);
+		System::assert_has_event(Event::SocialToken(SocialTokenEvent::Created(0, 1, 1_000)));
+		System::assert_last_event(Event::SocialToken(SocialTokenEvent::Issued(0, 1, 100)));
+	});
+}
+
+#[test]
+fn creation_checks_metadata_and_cap() {
+	new_test_ext().execute_with(|| {
+		let create = |name: &[u8], initial_supply| {
+			SocialToken::create(
+				Origin::signed(1),
+				name.to_vec(),
+				b"BIN".to_vec(),
+				18,
+				1_000,
+				initial_supply,
+				IssuancePolicy::Fixed,
+			)
+		};
+		assert_noop!(create(b"", 0), Error::<Test>::BadMetadata);
+		assert_noop!(create(b"Too long a name", 0), Error::<Test>::BadMetadata);
+		assert_noop!(create(b"Bein", 1_001), Error::<Test>::CapExceeded);
+	});
+}
+
+#[test]
+fn only_the_owner_mints_a_mintable_token_up_to_its_cap() {
+	new_test_ext().execute_with(|| {
+		create(0, IssuancePolicy::Mintable);
+		assert_noop!(SocialToken::mint(Origin::signed(2), 0, 2, 10), Error::<Test>::NoPermission);
+
+		assert_ok!(SocialToken::mint(Origin::signed(1), 0, 2, 1_000));
+		assert_eq!(SocialToken::balance_of(0, 2), 1_000);
+		assert_noop!(SocialToken::mint(Origin::signed(1), 0, 2, 1), Error::<Test>::CapExceeded);
+	});
+}
+
+#[test]
+fn fixed_token_cannot_be_minted() {
+	new_test_ext().execute_with(|| {
+		create(100, IssuancePolicy::Fixed);
+		assert_noop!(SocialToken::mint(Origin::signed(1), 0, 1, 10), Error::<Test>::NotMintable);
+	});
+}
+
+#[test]
+fn transfers_move_balances() {
+	new_test_ext().execute_with(|| {
+		create(100, IssuancePolicy::Fixed);
+
+		assert_ok!(SocialToken::transfer(Origin::signed(1), 0, 2, 40));
+		assert_eq!(SocialToken::balance_of(0, 1), 60);
+		assert_eq!(SocialToken::balance_of(0, 2), 40);
+		System::assert_last_event(Event::SocialToken(SocialTokenEvent::Transferred(0, 1, 2, 40)));
+		assert_noop!(
+			SocialToken::transfer(Origin::signed(2), 0, 1, 41),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_noop!(
+			SocialToken::transfer(Origin::signed(1), 1, 2, 1),
+			Error::<Test>::UnknownToken
+		);
+	});
+}
+
+#[test]
+fn transfer_from_spends_the_allowance() {
+	new_test_ext().execute_with(|| {
+		create(100, IssuancePolicy::Fixed);
+		assert_ok!(SocialToken::approve(Origin::signed(1), 0, 2, 50));
+
+		assert_ok!(SocialToken::transfer_from(Origin::signed(2), 0, 1, 3, 30));
+		assert_eq!(SocialToken::allowance(0, (1, 2)), 20);
+		assert_eq!(SocialToken::balance_of(0, 3), 30);
+		assert_noop!(
+			SocialToken::transfer_from(Origin::signed(2), 0, 1, 3, 21),
+			Error::<Test>::InsufficientAllowance
+		);
+	});
+}
+
+#[test]
+fn burning_reduces_the_supply() {
+	new_test_ext().execute_with(|| {
+		create(100, IssuancePolicy::Fixed);
+
+		assert_ok!(SocialToken::burn(Origin::signed(1), 0, 30));
+		assert_eq!(SocialToken::total_supply(0), 70);
+		assert_noop!(
+			SocialToken::burn(Origin::signed(1), 0, 71),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn token_addresses_map_back_to_their_ids() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(SocialToken::token_id(SocialToken::token_address(7)), Some(7));
+		assert_eq!(SocialToken::token_id(H160::repeat_byte(0xff)), None);
+		assert_eq!(SocialToken::token_id(H160::zero()), None);
+	});
+}
+
+#[test]
+fn only_a_token_without_supply_can_be_destroyed() {
+	new_test_ext().execute_with(|| {
+		create(100, IssuancePolicy::Mintable);
+		assert_noop!(SocialToken::destroy(Origin::signed(1), 0), Error::<Test>::InUse);
+		assert_ok!(SocialToken::burn(Origin::signed(1), 0, 100));
+		assert_noop!(SocialToken::destroy(Origin::signed(2), 0), Error::<Test>::NoPermission);
+		assert_noop!(
+			SocialToken::destroy_accounts(Origin::signed(2), 0),
+			Error::<Test>::NotDestroying
+		);
+
+		assert_ok!(SocialToken::destroy(Origin::signed(1), 0));
+		System::assert_last_event(Event::SocialToken(SocialTokenEvent::DestructionStarted(0)));
+		assert_noop!(SocialToken::destroy(Origin::signed(1), 0), Error::<Test>::Destroying);
+		assert_noop!(SocialToken::mint(Origin::signed(1), 0, 1, 10), Error::<Test>::Destroying);
+		assert_noop!(SocialToken::transfer(Origin::signed(1), 0, 2, 0), Error::<Test>::Destroying);
+		assert_noop!(SocialToken::approve(Origin::signed(1), 0, 2, 10), Error::<Test>::Destroying);
+	});
+}
+
+#[test]
+fn destroyed_token_is_cleared_in_bounded_steps() {
+	new_test_ext().execute_with(|| {
+		create(100, IssuancePolicy::Fixed);
+		assert_ok!(SocialToken::transfer(Origin::signed(1), 0, 2, 50));
+		assert_ok!(SocialToken::approve(Origin::signed(1), 0, 2, 10));
+		assert_ok!(SocialToken::burn(Origin::signed(1), 0, 50));
+		assert_ok!(SocialToken::burn(Origin::signed(2), 0, 50));
+		assert_ok!(SocialToken::destroy(Origin::signed(1), 0));
+
+		// Two balances and an allowance, two at a time.
+		assert_ok!(SocialToken::destroy_accounts(Origin::signed(2), 0));
+		assert!(SocialToken::tokens(0).is_some());
+		assert_ok!(SocialToken::destroy_accounts(Origin::signed(2), 0));
+
+		assert!(SocialToken::tokens(0).is_none());
+		assert_eq!(SocialToken::allowance(0, (1, 2)), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(!pallet_evm::AccountCodes::<Test>::contains_key(SocialToken::token_address(0)));
+		System::assert_last_event(Event::SocialToken(SocialTokenEvent::Destroyed(0)));
+	});
+}
@@ -0,0 +1,293 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Tips for creators.
+//!
+//! `tip` sends BIC straight to a creator along with a short memo, and records it in the
+//! creator's tip jar: lifetime totals and per-period stats. Each period also keeps a
+//! leaderboard of the most tipped creators. Periods older than `StatsHistory` periods are
+//! pruned, oldest first and at most `MaxPrunedPerBlock` creator stats per block. The `Tipped`
+//! event carries everything a social feed needs to show the tip.
+//!
+//! Tipping is meant to be free for stakers: the runtime lets `tip` through
+//! `pallet_feeless::feeless_call`.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	ensure,
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement},
+	weights::Weight,
+	RuntimeDebug,
+};
+use frame_system::{ensure_signed, pallet_prelude::*};
+use scale_info::TypeInfo;
+use sp_runtime::traits::{SaturatedConversion, Saturating, StaticLookup, Zero};
+use sp_std::vec::Vec;
+
+/// Index of a stats period, counted from genesis.
+pub type PeriodIndex = u32;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+pub trait WeightInfo {
+	fn tip() -> Weight;
+	fn prune_period() -> Weight;
+	fn prune_creator_stats(n: u32) -> Weight;
+}
+
+impl WeightInfo for () {
+	fn tip() -> Weight {
+		0
+	}
+	fn prune_period() -> Weight {
+		0
+	}
+	fn prune_creator_stats(_n: u32) -> Weight {
+		0
+	}
+}
+
+/// Amount and number of tips.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+pub struct TipStats<Balance> {
+	pub amount: Balance,
+	pub count: u32,
+}
+
+impl<Balance: Saturating + Copy> TipStats<Balance> {
+	fn record(&mut self, amount: Balance) {
+		self.amount = self.amount.saturating_add(amount);
+		self.count = self.count.saturating_add(1);
+	}
+}
+
+/// Everything a creator has received.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+pub struct TipJar<Balance, BlockNumber> {
+	pub received: TipStats<Balance>,
+	pub last_tip: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency tips are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Smallest tip accepted.
+		#[pallet::constant]
+		type MinTip: Get<BalanceOf<Self>>;
+
+		/// Maximum length of a tip memo.
+		#[pallet::constant]
+		type MaxMemoLength: Get<u32>;
+
+		/// Length of a stats period, in blocks.
+		#[pallet::constant]
+		type Period: Get<Self::BlockNumber>;
+
+		/// Number of creators kept on each period leaderboard.
+		#[pallet::constant]
+		type LeaderboardSize: Get<u32>;
+
+		/// Number of past periods whose stats are kept.
+		#[pallet::constant]
+		type StatsHistory: Get<PeriodIndex>;
+
+		/// Most creator period stats pruned in one block. Expired periods with more are
+		/// pruned over several blocks.
+		#[pallet::constant]
+		type MaxPrunedPerBlock: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Lifetime tips of each creator.
+	#[pallet::storage]
+	#[pallet::getter(fn tip_jar)]
+	pub type TipJars<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		TipJar<BalanceOf<T>, T::BlockNumber>,
+		ValueQuery,
+	>;
+
+	/// Tips received by each creator in a period.
+	#[pallet::storage]
+	#[pallet::getter(fn creator_period_stats)]
+	pub type CreatorPeriodStats<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PeriodIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		TipStats<BalanceOf<T>>,
+		ValueQuery,
+	>;
+
+	/// Tips sent across the chain in a period.
+	#[pallet::storage]
+	#[pallet::getter(fn period_stats)]
+	pub type PeriodStats<T: Config> =
+		StorageMap<_, Twox64Concat, PeriodIndex, TipStats<BalanceOf<T>>, ValueQuery>;
+
+	/// Most tipped creators of a period, by amount received, highest first.
+	#[pallet::storage]
+	#[pallet::getter(fn leaderboard)]
+	pub type Leaderboards<T: Config> =
+		StorageMap<_, Twox64Concat, PeriodIndex, Vec<(T::AccountId, BalanceOf<T>)>, ValueQuery>;
+
+	/// Oldest period whose stats have not been pruned yet.
+	#[pallet::storage]
+	#[pallet::getter(fn next_to_prune)]
+	pub type NextToPrune<T: Config> = StorageValue<_, PeriodIndex, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A creator was tipped. \[from, to, amount, memo\]
+		Tipped(T::AccountId, T::AccountId, BalanceOf<T>, Vec<u8>),
+		/// A period ended. \[period, amount, count\]
+		PeriodClosed(PeriodIndex, BalanceOf<T>, u32),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Tip is below `MinTip`
+		TipTooLow,
+		/// Memo is longer than `MaxMemoLength`
+		MemoTooLong,
+		/// Accounts cannot tip themselves
+		SelfTip,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut weight = Self::prune_expired(now);
+
+			let period = T::Period::get();
+			if !period.is_zero() && (now % period).is_zero() && !now.is_zero() {
+				let closed = Self::period_index(now).saturating_sub(1);
+				let stats = Self::period_stats(closed);
+				Self::deposit_event(Event::PeriodClosed(closed, stats.amount, stats.count));
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			}
+			weight
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Send `amount` to `recipient` as a tip, with a memo shown alongside it.
+		#[pallet::weight(T::WeightInfo::tip())]
+		pub fn tip(
+			origin: OriginFor<T>,
+			recipient: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+			memo: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let recipient = T::Lookup::lookup(recipient)?;
+
+			ensure!(who != recipient, Error::<T>::SelfTip);
+			ensure!(amount >= T::MinTip::get(), Error::<T>::TipTooLow);
+			ensure!(memo.len() <= T::MaxMemoLength::get() as usize, Error::<T>::MemoTooLong);
+
+			T::Currency::transfer(&who, &recipient, amount, ExistenceRequirement::KeepAlive)?;
+			Self::record_tip(&recipient, amount);
+			Self::deposit_event(Event::Tipped(who, recipient, amount, memo));
+
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Period that block `n` falls in.
+	pub fn period_index(n: T::BlockNumber) -> PeriodIndex {
+		let period = T::Period::get().max(1u32.into());
+		(n / period).saturated_into()
+	}
+
+	/// Prune the stats of periods older than `StatsHistory`, oldest first. Removing a creator
+	/// entry or finishing a period each take one of the `MaxPrunedPerBlock` allowed per block.
+	fn prune_expired(now: T::BlockNumber) -> Weight {
+		let current = Self::period_index(now);
+		let history = T::StatsHistory::get();
+		let limit = T::MaxPrunedPerBlock::get();
+
+		let mut next = Self::next_to_prune();
+		let mut budget = limit;
+		let mut periods: u32 = 0;
+		while budget > 0 && next.saturating_add(history) < current {
+			let drained =
+				CreatorPeriodStats::<T>::drain_prefix(next).take(budget as usize).count() as u32;
+			budget -= drained;
+			if budget == 0 {
+				// The period may have more entries left for the next block.
+				break
+			}
+			budget -= 1;
+			PeriodStats::<T>::remove(next);
+			Leaderboards::<T>::remove(next);
+			next = next.saturating_add(1);
+			periods += 1;
+		}
+
+		let mut weight = T::DbWeight::get().reads(1);
+		if periods > 0 {
+			NextToPrune::<T>::put(next);
+			weight = weight.saturating_add(T::DbWeight::get().writes(1));
+		}
+		let removed = limit - budget - periods;
+		weight
+			.saturating_add(T::WeightInfo::prune_creator_stats(removed))
+			.saturating_add(T::WeightInfo::prune_period().saturating_mul(periods as Weight))
+	}
+
+	fn record_tip(recipient: &T::AccountId, amount: BalanceOf<T>) {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let period = Self::period_index(now);
+
+		TipJars::<T>::mutate(recipient, |jar| {
+			jar.received.record(amount);
+			jar.last_tip = now;
+		});
+		PeriodStats::<T>::mutate(period, |stats| stats.record(amount));
+		let received = CreatorPeriodStats::<T>::mutate(period, recipient, |stats| {
+			stats.record(amount);
+			stats.amount
+		});
+
+		Leaderboards::<T>::mutate(period, |board| {
+			board.retain(|(creator, _)| creator != recipient);
+			let position = board
+				.iter()
+				.position(|(_, amount)| *amount < received)
+				.unwrap_or_else(|| board.len());
+			board.insert(position, (recipient.clone(), received));
+			board.truncate(T::LeaderboardSize::get() as usize);
+		});
+	}
+}
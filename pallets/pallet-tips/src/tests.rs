@@ -0,0 +1,92 @@
+use crate::{mock::*, Error, Event as TipsEvent, TipStats};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+
+fn tip(from: u64, to: u64, amount: u64) {
+	assert_ok!(Tips::tip(Origin::signed(from), to, amount, b"gm".to_vec()));
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Tips::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn tip_pays_the_creator_and_fills_its_jar() {
+	new_test_ext().execute_with(|| {
+		tip(1, 2, 10);
+
+		assert_eq!(Balances::free_balance(1), 990);
+		assert_eq!(Balances::free_balance(2), 1_010);
+		let jar = Tips::tip_jar(2);
+		assert_eq!(jar.received, TipStats { amount: 10, count: 1 });
+		assert_eq!(jar.last_tip, 1);
+		assert_eq!(Tips::creator_period_stats(0, 2), TipStats { amount: 10, count: 1 });
+		assert_eq!(Tips::period_stats(0), TipStats { amount: 10, count: 1 });
+		System::assert_last_event(Event::Tips(TipsEvent::Tipped(1, 2, 10, b"gm".to_vec())));
+	});
+}
+
+#[test]
+fn tips_are_checked() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Tips::tip(Origin::signed(1), 1, 10, vec![]), Error::<Test>::SelfTip);
+		assert_noop!(Tips::tip(Origin::signed(1), 2, 4, vec![]), Error::<Test>::TipTooLow);
+		assert_noop!(
+			Tips::tip(Origin::signed(1), 2, 10, b"too long!".to_vec()),
+			Error::<Test>::MemoTooLong
+		);
+	});
+}
+
+#[test]
+fn leaderboard_keeps_the_most_tipped_creators() {
+	new_test_ext().execute_with(|| {
+		tip(1, 2, 10);
+		tip(1, 3, 20);
+		tip(1, 4, 15);
+		assert_eq!(Tips::leaderboard(0), vec![(3, 20), (4, 15)]);
+
+		tip(5, 2, 20);
+		assert_eq!(Tips::leaderboard(0), vec![(2, 30), (3, 20)]);
+	});
+}
+
+#[test]
+fn period_close_is_announced() {
+	new_test_ext().execute_with(|| {
+		tip(1, 2, 10);
+		tip(3, 2, 5);
+		run_to_block(10);
+
+		System::assert_last_event(Event::Tips(TipsEvent::PeriodClosed(0, 15, 2)));
+		tip(1, 2, 10);
+		assert_eq!(Tips::period_stats(1), TipStats { amount: 10, count: 1 });
+		assert_eq!(Tips::tip_jar(2).received, TipStats { amount: 25, count: 3 });
+	});
+}
+
+#[test]
+fn expired_periods_are_pruned_over_several_blocks() {
+	new_test_ext().execute_with(|| {
+		for creator in 2..=5 {
+			tip(1, creator, 10);
+		}
+
+		// Period 0 is kept through period 1 and expires with period 2.
+		run_to_block(19);
+		assert_eq!(Tips::period_stats(0).count, 4);
+		run_to_block(20);
+		assert_eq!(Tips::period_stats(0).count, 4);
+		assert_eq!((2..=5).filter(|c| Tips::creator_period_stats(0, c).count == 0).count(), 3);
+
+		run_to_block(21);
+		assert_eq!(Tips::period_stats(0), TipStats::default());
+		assert_eq!(Tips::creator_period_stats(0, 2), TipStats::default());
+		assert!(Tips::leaderboard(0).is_empty());
+		assert_eq!(Tips::next_to_prune(), 1);
+		// Lifetime totals stay.
+		assert_eq!(Tips::tip_jar(2).received, TipStats { amount: 10, count: 1 });
+	});
+}
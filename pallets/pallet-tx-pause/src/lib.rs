@@ -0,0 +1,219 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Emergency pause of pallets and calls.
+//!
+//! `PauseOrigin` can pause a whole pallet, or a single call of it, by name. The runtime puts
+//! `PausedCallFilter` in its `BaseCallFilter`, so paused calls fail to dispatch however they
+//! are reached: directly, through a proxy, a batch or `feeless_call`. Self-contained calls
+//! such as `Ethereum::transact` should also be checked when they are validated so that they
+//! do not enter the transaction pool, and precompiles that act for a pallet should check
+//! `is_paused` for the call they stand for.
+//!
+//! Calls of this pallet and of the `UnpausablePallets` can never be paused, so that blocks
+//! keep being produced and governance can always lift a pause.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use frame_support::{
+	ensure,
+	pallet_prelude::*,
+	traits::{CallMetadata, Contains, GetCallMetadata, PalletInfoAccess},
+	weights::Weight,
+};
+use frame_system::pallet_prelude::*;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+pub trait WeightInfo {
+	fn pause() -> Weight;
+	fn unpause() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn pause() -> Weight {
+		0
+	}
+	fn unpause() -> Weight {
+		0
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin allowed to pause pallets and calls.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to lift a pause.
+		type UnpauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Pallets that can never be paused, by name: those whose inherents and calls blocks
+		/// need, and those governance lifts pauses through.
+		type UnpausablePallets: Contains<Vec<u8>>;
+
+		/// Pallets and calls of the runtime, by name, so that a misspelled one is not paused
+		/// in vain. `KnownCallsOf` reads them from the runtime's call metadata.
+		type KnownCalls: Contains<(Vec<u8>, Option<Vec<u8>>)>;
+
+		/// Maximum length of a pallet or call name.
+		#[pallet::constant]
+		type MaxNameLen: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Paused calls, by pallet name and call name. A `None` call name pauses the whole
+	/// pallet.
+	#[pallet::storage]
+	#[pallet::getter(fn paused)]
+	pub type PausedCalls<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Vec<u8>,
+		Blake2_128Concat,
+		Option<Vec<u8>>,
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event {
+		/// A pallet or one of its calls was paused. \[pallet, call\]
+		Paused(Vec<u8>, Option<Vec<u8>>),
+		/// A pause was lifted. \[pallet, call\]
+		Unpaused(Vec<u8>, Option<Vec<u8>>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// This pallet cannot be paused
+		Unpausable,
+		/// Pallet or call name is longer than `MaxNameLen`
+		NameTooLong,
+		/// No such pallet or call in the runtime
+		UnknownCall,
+		/// Pallet or call is already paused
+		AlreadyPaused,
+		/// Pallet or call is not paused
+		NotPaused,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause a call of a pallet, or every call of it if `call_name` is `None`.
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			call_name: Option<Vec<u8>>,
+		) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			let limit = T::MaxNameLen::get() as usize;
+			ensure!(
+				pallet_name.len() <= limit && call_name.as_ref().map_or(true, |name| name.len() <= limit),
+				Error::<T>::NameTooLong
+			);
+			ensure!(
+				pallet_name.as_slice() != <Self as PalletInfoAccess>::name().as_bytes() &&
+					!T::UnpausablePallets::contains(&pallet_name),
+				Error::<T>::Unpausable
+			);
+			ensure!(
+				T::KnownCalls::contains(&(pallet_name.clone(), call_name.clone())),
+				Error::<T>::UnknownCall
+			);
+			ensure!(
+				!PausedCalls::<T>::contains_key(&pallet_name, &call_name),
+				Error::<T>::AlreadyPaused
+			);
+
+			PausedCalls::<T>::insert(&pallet_name, &call_name, ());
+			Self::deposit_event(Event::Paused(pallet_name, call_name));
+
+			Ok(().into())
+		}
+
+		/// Lift a pause set by `pause` with the same names.
+		#[pallet::weight(T::WeightInfo::unpause())]
+		pub fn unpause(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			call_name: Option<Vec<u8>>,
+		) -> DispatchResultWithPostInfo {
+			T::UnpauseOrigin::ensure_origin(origin)?;
+			ensure!(
+				PausedCalls::<T>::contains_key(&pallet_name, &call_name),
+				Error::<T>::NotPaused
+			);
+
+			PausedCalls::<T>::remove(&pallet_name, &call_name);
+			Self::deposit_event(Event::Unpaused(pallet_name, call_name));
+
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether a call, given by its metadata, is paused on its own or with its pallet.
+	pub fn is_paused(metadata: &CallMetadata) -> bool {
+		let pallet_name = metadata.pallet_name.as_bytes();
+		PausedCalls::<T>::contains_key(pallet_name, None::<Vec<u8>>) ||
+			PausedCalls::<T>::contains_key(
+				pallet_name,
+				Some(metadata.function_name.as_bytes().to_vec()),
+			)
+	}
+}
+
+/// Pallets with calls and their calls, as listed in the metadata of the runtime's `Call`.
+pub struct KnownCallsOf<T>(PhantomData<T>);
+impl<T: Config> Contains<(Vec<u8>, Option<Vec<u8>>)> for KnownCallsOf<T>
+where
+	<T as frame_system::Config>::Call: GetCallMetadata,
+{
+	fn contains((pallet_name, call_name): &(Vec<u8>, Option<Vec<u8>>)) -> bool {
+		type CallOf<T> = <T as frame_system::Config>::Call;
+		let pallet_name = match sp_std::str::from_utf8(pallet_name) {
+			Ok(name) => name,
+			Err(_) => return false,
+		};
+		CallOf::<T>::get_module_names().contains(&pallet_name) &&
+			call_name.as_ref().map_or(true, |call_name| {
+				CallOf::<T>::get_call_names(pallet_name)
+					.iter()
+					.any(|name| name.as_bytes() == call_name.as_slice())
+			})
+	}
+}
+
+/// Lets through every call that is not paused.
+pub struct PausedCallFilter<T>(PhantomData<T>);
+impl<T: Config> Contains<<T as frame_system::Config>::Call> for PausedCallFilter<T>
+where
+	<T as frame_system::Config>::Call: GetCallMetadata,
+{
+	fn contains(call: &<T as frame_system::Config>::Call) -> bool {
+		!Pallet::<T>::is_paused(&call.get_call_metadata())
+	}
+}
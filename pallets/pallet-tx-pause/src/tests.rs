@@ -0,0 +1,111 @@
+use crate::{mock::*, Error, Event as TxPauseEvent};
+use frame_support::{assert_noop, assert_ok, dispatch::Dispatchable, traits::Contains};
+use sp_runtime::DispatchError;
+
+fn transfer() -> Call {
+	Call::Balances(pallet_balances::Call::transfer { dest: 2, value: 1 })
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn is_allowed(call: &Call) -> bool {
+	<Test as frame_system::Config>::BaseCallFilter::contains(call)
+}
+
+#[test]
+fn pausing_a_pallet_filters_all_its_calls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(Origin::root(), b"Balances".to_vec(), None));
+
+		assert!(!is_allowed(&transfer()));
+		assert!(is_allowed(&remark()));
+		assert!(transfer().dispatch(Origin::signed(1)).is_err());
+		assert_eq!(Balances::free_balance(2), 0);
+		System::assert_last_event(Event::TxPause(TxPauseEvent::Paused(b"Balances".to_vec(), None)));
+	});
+}
+
+#[test]
+fn pausing_a_call_leaves_the_rest_of_its_pallet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(Origin::root(), b"Balances".to_vec(), Some(b"transfer".to_vec())));
+
+		assert!(!is_allowed(&transfer()));
+		assert!(is_allowed(&Call::Balances(pallet_balances::Call::transfer_keep_alive {
+			dest: 2,
+			value: 1,
+		})));
+	});
+}
+
+#[test]
+fn unpausing_lets_calls_through_again() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(Origin::root(), b"Balances".to_vec(), None));
+		assert_noop!(
+			TxPause::pause(Origin::root(), b"Balances".to_vec(), None),
+			Error::<Test>::AlreadyPaused
+		);
+		assert_ok!(TxPause::unpause(Origin::root(), b"Balances".to_vec(), None));
+
+		assert!(is_allowed(&transfer()));
+		assert_ok!(transfer().dispatch(Origin::signed(1)));
+		assert_noop!(
+			TxPause::unpause(Origin::root(), b"Balances".to_vec(), None),
+			Error::<Test>::NotPaused
+		);
+	});
+}
+
+#[test]
+fn unpausable_pallets_cannot_be_paused() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::pause(Origin::root(), b"System".to_vec(), None),
+			Error::<Test>::Unpausable
+		);
+		assert_noop!(
+			TxPause::pause(Origin::root(), b"System".to_vec(), Some(b"remark".to_vec())),
+			Error::<Test>::Unpausable
+		);
+		assert_noop!(
+			TxPause::pause(Origin::root(), b"TxPause".to_vec(), None),
+			Error::<Test>::Unpausable
+		);
+	});
+}
+
+#[test]
+fn only_known_pallets_and_calls_can_be_paused() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::pause(Origin::root(), b"Balance".to_vec(), None),
+			Error::<Test>::UnknownCall
+		);
+		assert_noop!(
+			TxPause::pause(Origin::root(), b"Balances".to_vec(), Some(b"transfr".to_vec())),
+			Error::<Test>::UnknownCall
+		);
+		assert_ok!(TxPause::pause(
+			Origin::root(),
+			b"Balances".to_vec(),
+			Some(b"set_balance".to_vec())
+		));
+	});
+}
+
+#[test]
+fn pause_checks_origin_and_name_length() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::pause(Origin::signed(1), b"Balances".to_vec(), None),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TxPause::pause(Origin::root(), [b'a'; 17].to_vec(), None),
+			Error::<Test>::NameTooLong
+		);
+	});
+}
@@ -0,0 +1,256 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Proof-of-authority validator set.
+//!
+//! Governance adds and removes validators with `add_validator` and `remove_validator`. The
+//! pallet acts as the `SessionManager` of `pallet_session`, so changes take effect at the
+//! next session rotation and Aura and Grandpa follow them. A new validator must register
+//! its keys with `Session::set_keys` before it can author blocks.
+//!
+//! The pallet is also the offence handler of `pallet_offences`: a reported validator is
+//! disabled for the rest of the session and removed from the set at the next rotation.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use frame_support::{
+	ensure,
+	pallet_prelude::*,
+	weights::Weight,
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::Perbill;
+use sp_staking::{
+	offence::{OffenceDetails, OnOffenceHandler},
+	SessionIndex,
+};
+use sp_std::vec::Vec;
+
+pub trait WeightInfo {
+	fn add_validator() -> Weight;
+	fn remove_validator() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn add_validator() -> Weight {
+		0
+	}
+	fn remove_validator() -> Weight {
+		0
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin allowed to add or remove validators.
+		type AddRemoveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Minimum number of validators that must stay in the set.
+		#[pallet::constant]
+		type MinAuthorities: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Accounts that will validate from the next session on.
+	#[pallet::storage]
+	#[pallet::getter(fn validators)]
+	pub type Validators<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// Whether `Validators` changed since the last session rotation.
+	#[pallet::storage]
+	pub(super) type ValidatorsChanged<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A validator was added, effective next session. \[validator\]
+		ValidatorAdded(T::AccountId),
+		/// A validator was removed, effective next session. \[validator\]
+		ValidatorRemoved(T::AccountId),
+		/// A validator was reported for an offence and disabled. \[validator, slash_fraction\]
+		ValidatorDisabled(T::AccountId, Perbill),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Account is already a validator
+		AlreadyValidator,
+		/// Account is not a validator
+		NotValidator,
+		/// Removing the validator would leave fewer than `MinAuthorities`
+		TooFewValidators,
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub validators: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig { validators: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(Validators::<T>::get().is_empty(), "Validators are already initialized!");
+			Validators::<T>::put(&self.validators);
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add a validator to the set, effective from the next session.
+		#[pallet::weight(T::WeightInfo::add_validator())]
+		pub fn add_validator(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			Validators::<T>::try_mutate(|validators| -> DispatchResult {
+				ensure!(!validators.contains(&validator), Error::<T>::AlreadyValidator);
+				validators.push(validator.clone());
+				Ok(())
+			})?;
+			ValidatorsChanged::<T>::put(true);
+			Self::deposit_event(Event::ValidatorAdded(validator));
+
+			Ok(().into())
+		}
+
+		/// Remove a validator from the set, effective from the next session.
+		#[pallet::weight(T::WeightInfo::remove_validator())]
+		pub fn remove_validator(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			Validators::<T>::try_mutate(|validators| -> DispatchResult {
+				let index = validators
+					.iter()
+					.position(|v| v == &validator)
+					.ok_or(Error::<T>::NotValidator)?;
+				ensure!(
+					validators.len() > T::MinAuthorities::get() as usize,
+					Error::<T>::TooFewValidators
+				);
+				validators.remove(index);
+				Ok(())
+			})?;
+			ValidatorsChanged::<T>::put(true);
+			Self::deposit_event(Event::ValidatorRemoved(validator));
+
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Drop `validator` from the set at the next rotation, keeping at least `MinAuthorities`.
+	fn remove_offender(validator: &T::AccountId) -> bool {
+		Validators::<T>::mutate(|validators| {
+			match validators.iter().position(|v| v == validator) {
+				Some(index) if validators.len() > T::MinAuthorities::get() as usize => {
+					validators.remove(index);
+					true
+				}
+				_ => false,
+			}
+		})
+	}
+}
+
+impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+	fn new_session(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+		if ValidatorsChanged::<T>::take() {
+			Some(Self::validators())
+		} else {
+			None
+		}
+	}
+
+	fn new_session_genesis(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+		Some(Self::validators())
+	}
+
+	fn end_session(_end_index: SessionIndex) {}
+
+	fn start_session(_start_index: SessionIndex) {}
+}
+
+/// Validators are fully identified by their account, which lets `pallet_session::historical`
+/// prove key ownership for equivocation reports.
+impl<T: Config> pallet_session::historical::SessionManager<T::AccountId, T::AccountId>
+	for Pallet<T>
+{
+	fn new_session(new_index: SessionIndex) -> Option<Vec<(T::AccountId, T::AccountId)>> {
+		<Self as pallet_session::SessionManager<_>>::new_session(new_index)
+			.map(|validators| validators.into_iter().map(|v| (v.clone(), v)).collect())
+	}
+
+	fn new_session_genesis(new_index: SessionIndex) -> Option<Vec<(T::AccountId, T::AccountId)>> {
+		<Self as pallet_session::SessionManager<_>>::new_session_genesis(new_index)
+			.map(|validators| validators.into_iter().map(|v| (v.clone(), v)).collect())
+	}
+
+	fn end_session(end_index: SessionIndex) {
+		<Self as pallet_session::SessionManager<_>>::end_session(end_index)
+	}
+
+	fn start_session(start_index: SessionIndex) {
+		<Self as pallet_session::SessionManager<_>>::start_session(start_index)
+	}
+}
+
+/// There is no bond to slash in a proof-of-authority set, so an offence disables the
+/// validator for the current session and removes it from the set.
+impl<T> OnOffenceHandler<T::AccountId, (T::AccountId, T::AccountId), Weight> for Pallet<T>
+where
+	T: Config + pallet_session::Config<ValidatorId = <T as frame_system::Config>::AccountId>,
+{
+	fn on_offence(
+		offenders: &[OffenceDetails<T::AccountId, (T::AccountId, T::AccountId)>],
+		slash_fraction: &[Perbill],
+		_session: SessionIndex,
+	) -> Weight {
+		let mut weight: Weight = 0;
+		let db_weight = <T as frame_system::Config>::DbWeight::get();
+
+		for (details, fraction) in offenders.iter().zip(slash_fraction) {
+			let (validator, _) = &details.offender;
+			let _ = pallet_session::Pallet::<T>::disable(validator);
+			if Self::remove_offender(validator) {
+				ValidatorsChanged::<T>::put(true);
+			}
+			Self::deposit_event(Event::ValidatorDisabled(validator.clone(), *fraction));
+			weight = weight.saturating_add(db_weight.reads_writes(3, 3));
+		}
+
+		weight
+	}
+}
@@ -0,0 +1,88 @@
+use crate::{mock::*, Error, Event as ValidatorSetEvent};
+use frame_support::{assert_noop, assert_ok};
+use pallet_session::SessionManager;
+use sp_runtime::{traits::BadOrigin, Perbill};
+use sp_staking::offence::{OffenceDetails, OnOffenceHandler};
+
+fn report(offender: u64) {
+	<ValidatorSet as OnOffenceHandler<_, _, _>>::on_offence(
+		&[OffenceDetails { offender: (offender, offender), reporters: vec![] }],
+		&[Perbill::from_percent(10)],
+		0,
+	);
+}
+
+#[test]
+fn added_validator_joins_at_the_next_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+
+		assert_eq!(ValidatorSet::validators(), vec![1, 2, 3, 4]);
+		System::assert_last_event(Event::ValidatorSet(ValidatorSetEvent::ValidatorAdded(4)));
+		assert_eq!(<ValidatorSet as SessionManager<_>>::new_session(1), Some(vec![1, 2, 3, 4]));
+		// Unchanged since.
+		assert_eq!(<ValidatorSet as SessionManager<_>>::new_session(2), None);
+	});
+}
+
+#[test]
+fn only_the_origin_can_change_validators() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::signed(1), 4), BadOrigin);
+		assert_noop!(ValidatorSet::remove_validator(Origin::signed(1), 3), BadOrigin);
+	});
+}
+
+#[test]
+fn validator_cannot_be_added_twice() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 1),
+			Error::<Test>::AlreadyValidator
+		);
+	});
+}
+
+#[test]
+fn removal_keeps_the_minimum_of_validators() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 4),
+			Error::<Test>::NotValidator
+		);
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+		System::assert_last_event(Event::ValidatorSet(ValidatorSetEvent::ValidatorRemoved(3)));
+
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 2),
+			Error::<Test>::TooFewValidators
+		);
+		assert_eq!(<ValidatorSet as SessionManager<_>>::new_session(1), Some(vec![1, 2]));
+	});
+}
+
+#[test]
+fn offender_is_disabled_and_removed_at_the_next_session() {
+	new_test_ext().execute_with(|| {
+		report(2);
+
+		assert_eq!(Session::disabled_validators(), vec![1]);
+		System::assert_last_event(Event::ValidatorSet(ValidatorSetEvent::ValidatorDisabled(
+			2,
+			Perbill::from_percent(10),
+		)));
+		assert_eq!(<ValidatorSet as SessionManager<_>>::new_session(1), Some(vec![1, 3]));
+	});
+}
+
+#[test]
+fn offender_stays_in_a_set_at_its_minimum() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+		assert_eq!(<ValidatorSet as SessionManager<_>>::new_session(1), Some(vec![1, 2]));
+		report(2);
+
+		assert_eq!(ValidatorSet::validators(), vec![1, 2]);
+		assert_eq!(<ValidatorSet as SessionManager<_>>::new_session(2), None);
+	});
+}
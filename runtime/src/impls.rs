@@ -1,8 +1,31 @@
 use pallet_evm_account::MergeAccount;
-use crate::{AccountId, Balances};
-use sp_runtime::DispatchResult;
+use pallet_feeless::NestedFeelessCalls;
+use pallet_transaction_payment::OnChargeTransaction;
+use crate::{
+    AccountId, Aura, Balance, Balances, Call, Council, CouncilMembership, Democracy, EvmAccounts,
+    Grandpa, Proxy, Runtime, Scheduler, Session, System, TechnicalCommittee,
+    TechnicalMembership, Timestamp, Treasury, TxPause, MILLI_BIC,
+};
+use sp_core::H160;
+use sp_runtime::{
+    traits::{DispatchInfoOf, PostDispatchInfoOf, Zero},
+    transaction_validity::TransactionValidityError,
+    DispatchResult, Perbill,
+};
 use frame_support::transactional;
-use frame_support::traits::ReservableCurrency;
+use frame_support::traits::{
+    Contains, Currency, FindAuthor, Imbalance, InstanceFilter, OnUnbalanced, PalletInfoAccess,
+    ReservableCurrency,
+};
+use frame_support::weights::{
+    constants::ExtrinsicBaseWeight, WeightToFeeCoefficient, WeightToFeeCoefficients,
+    WeightToFeePolynomial,
+};
+use frame_support::ConsensusEngineId;
+use smallvec::smallvec;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
 
 pub struct MergeAccountEvm;
 impl MergeAccount<AccountId> for MergeAccountEvm {
@@ -18,3 +41,209 @@ impl MergeAccount<AccountId> for MergeAccountEvm {
         }
     }
 }
+
+/// Resolves the Aura slot author into the validator account that registered its session keys.
+pub type FindAuthorAccountId = pallet_session::FindAccountFromAuthorIndex<Runtime, Aura>;
+
+/// Resolves the block author into the EVM address of its validator account, if it has one.
+pub struct FindAuthorEvmAddress<F>(PhantomData<F>);
+impl<F: FindAuthor<AccountId>> FindAuthor<H160> for FindAuthorEvmAddress<F> {
+    fn find_author<'a, I>(digests: I) -> Option<H160>
+        where
+            I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+    {
+        EvmAccounts::evm_address_of(&F::find_author(digests)?)
+    }
+}
+
+/// Deposits the imbalance into the account of the current block author,
+/// falling back to the treasury when the author cannot be found.
+pub struct ToAuthor;
+impl OnUnbalanced<NegativeImbalance> for ToAuthor {
+    fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+        let digest = System::digest();
+        let pre_runtime_digests = digest.logs().iter().filter_map(|d| d.as_pre_runtime());
+        match FindAuthorAccountId::find_author(pre_runtime_digests) {
+            Some(author) => Balances::resolve_creating(&author, amount),
+            None => Treasury::on_unbalanced(amount),
+        }
+    }
+}
+
+/// Splits transaction fees between the treasury and the block author.
+///
+/// Fees are split 80% to the treasury and 20% to the author, tips go to the author in full.
+/// Used by both `pallet_transaction_payment` and `pallet_evm`.
+pub struct DealWithFees;
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+    fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+        if let Some(fees) = fees_then_tips.next() {
+            let mut split = fees.ration(80, 20);
+            if let Some(tips) = fees_then_tips.next() {
+                tips.merge_into(&mut split.1);
+            }
+            Treasury::on_unbalanced(split.0);
+            ToAuthor::on_unbalanced(split.1);
+        }
+    }
+
+    fn on_nonzero_unbalanced(fees: NegativeImbalance) {
+        let (to_treasury, to_author) = fees.ration(80, 20);
+        Treasury::on_unbalanced(to_treasury);
+        ToAuthor::on_unbalanced(to_author);
+    }
+}
+
+/// Calls stakers can make without fees through `Feeless::feeless_call`: everyday social
+/// actions such as tips, transfers, auction bids and staking, alone, batched or made
+/// through a proxy.
+pub struct FeelessCallFilter;
+impl Contains<Call> for FeelessCallFilter {
+    fn contains(call: &Call) -> bool {
+        match call {
+            Call::Utility(pallet_utility::Call::batch { calls }) |
+            Call::Utility(pallet_utility::Call::batch_all { calls }) => {
+                calls.iter().all(Self::contains)
+            }
+            Call::Proxy(pallet_proxy::Call::proxy { call, .. }) => Self::contains(call),
+            _ => matches!(
+                call,
+                Call::Tips(pallet_tips::Call::tip { .. }) |
+                    Call::Balances(pallet_balances::Call::transfer { .. }) |
+                    Call::Balances(pallet_balances::Call::transfer_keep_alive { .. }) |
+                    Call::SocialToken(pallet_social_token::Call::transfer { .. }) |
+                    Call::Nft(pallet_bein_nft::Call::transfer { .. }) |
+                    Call::AuctionHouse(pallet_auction_house::Call::bid { .. }) |
+                    Call::Feeless(pallet_feeless::Call::stake_bic { .. })
+            ),
+        }
+    }
+}
+
+/// `feeless_call`s made through `Utility::batch`, `Utility::batch_all` and `Proxy::proxy`,
+/// nested to any depth. A proxy call only counts if the signer is a proxy of the real account
+/// that may make it right away, as `Proxy::proxy` checks on dispatch.
+pub struct FeelessWrappers;
+impl FeelessWrappers {
+    /// Collect the `feeless_call`s `who` makes with `call` into `found`, telling whether
+    /// `call` is made of nothing else. A top-level `feeless_call` is not nested.
+    fn collect(
+        who: &AccountId,
+        call: &Call,
+        nested: bool,
+        found: &mut Vec<(AccountId, Call)>,
+    ) -> bool {
+        match call {
+            Call::Feeless(pallet_feeless::Call::feeless_call { call }) if nested => {
+                found.push((who.clone(), (**call).clone()));
+                true
+            }
+            Call::Utility(pallet_utility::Call::batch { calls }) |
+            Call::Utility(pallet_utility::Call::batch_all { calls }) => {
+                !calls.is_empty() && calls.iter().all(|call| Self::collect(who, call, true, found))
+            }
+            Call::Proxy(pallet_proxy::Call::proxy { real, force_proxy_type, call }) => {
+                match Proxy::find_proxy(real, who, *force_proxy_type) {
+                    Ok(proxy) if proxy.delay.is_zero() && proxy.proxy_type.filter(call) => {
+                        Self::collect(real, call, true, found)
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl NestedFeelessCalls<Runtime> for FeelessWrappers {
+    fn nested_feeless_calls(who: &AccountId, call: &Call) -> Option<Vec<(AccountId, Call)>> {
+        let mut found = Vec::new();
+        if Self::collect(who, call, false, &mut found) {
+            Some(found)
+        } else {
+            None
+        }
+    }
+}
+
+/// Charges transaction fees with `C`, except for wrappers of nothing but `feeless_call`s. Their
+/// calls are paid for by `feeless_call` out of bandwidth or balance, as `CheckFeeless` checked
+/// they can be.
+pub struct FeelessWrapperFees<C>(PhantomData<C>);
+impl<C: OnChargeTransaction<Runtime>> OnChargeTransaction<Runtime> for FeelessWrapperFees<C> {
+    type Balance = C::Balance;
+    type LiquidityInfo = C::LiquidityInfo;
+
+    fn withdraw_fee(
+        who: &AccountId,
+        call: &Call,
+        dispatch_info: &DispatchInfoOf<Call>,
+        fee: Self::Balance,
+        tip: Self::Balance,
+    ) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+        if FeelessWrappers::nested_feeless_calls(who, call).is_some() {
+            return Ok(Default::default());
+        }
+        C::withdraw_fee(who, call, dispatch_info, fee, tip)
+    }
+
+    fn correct_and_deposit_fee(
+        who: &AccountId,
+        dispatch_info: &DispatchInfoOf<Call>,
+        post_info: &PostDispatchInfoOf<Call>,
+        corrected_fee: Self::Balance,
+        tip: Self::Balance,
+        already_withdrawn: Self::LiquidityInfo,
+    ) -> Result<(), TransactionValidityError> {
+        C::correct_and_deposit_fee(
+            who,
+            dispatch_info,
+            post_info,
+            corrected_fee,
+            tip,
+            already_withdrawn,
+        )
+    }
+}
+
+/// Pallets `TxPause` can never pause: those that produce and finalize blocks, whose inherents
+/// must always dispatch, and governance, which must stay able to lift a pause.
+pub struct UnpausablePallets;
+impl Contains<Vec<u8>> for UnpausablePallets {
+    fn contains(pallet_name: &Vec<u8>) -> bool {
+        [
+            System::name(),
+            Timestamp::name(),
+            Aura::name(),
+            Grandpa::name(),
+            Session::name(),
+            Council::name(),
+            CouncilMembership::name(),
+            TechnicalCommittee::name(),
+            TechnicalMembership::name(),
+            Democracy::name(),
+            Scheduler::name(),
+            TxPause::name(),
+        ]
+        .iter()
+        .any(|name| name.as_bytes() == pallet_name.as_slice())
+    }
+}
+
+/// Converts weight into a fee in BIC: the base weight of an extrinsic costs one milli BIC,
+/// and fees grow linearly with weight from there.
+pub struct WeightToFee;
+impl WeightToFeePolynomial for WeightToFee {
+    type Balance = Balance;
+
+    fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+        let p = MILLI_BIC;
+        let q = Balance::from(ExtrinsicBaseWeight::get());
+        smallvec![WeightToFeeCoefficient {
+            degree: 1,
+            negative: false,
+            coeff_frac: Perbill::from_rational(p % q, q),
+            coeff_integer: p / q,
+        }]
+    }
+}
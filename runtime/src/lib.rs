@@ -6,26 +6,31 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
+use pallet_session::historical as pallet_session_historical;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{
-	crypto::{KeyTypeId, Public},
+	crypto::KeyTypeId,
+	u32_trait::{_1, _2, _3, _5},
 	OpaqueMetadata, H160, H256, U256,
 };
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify,
-		PostDispatchInfoOf, Dispatchable
+		AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, IdentifyAccount,
+		NumberFor, OpaqueKeys, Verify, PostDispatchInfoOf, Dispatchable
 	},
-	transaction_validity::{TransactionSource, TransactionValidity, TransactionValidityError},
-	ApplyExtrinsicResult, MultiSignature,
+	transaction_validity::{
+		InvalidTransaction, TransactionSource, TransactionValidity, TransactionValidityError,
+	},
+	ApplyExtrinsicResult, FixedPointNumber, MultiSignature,
 };
-use sp_std::{marker::PhantomData, prelude::*};
+use sp_std::prelude::*;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
@@ -38,25 +43,33 @@ pub use frame_support::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	ConsensusEngineId, StorageValue,
+	ConsensusEngineId, PalletId, StorageValue,
 };
+use frame_support::{traits::{Contains, InstanceFilter}, RuntimeDebug};
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment::CurrencyAdapter;
+use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Permill, Perquintill};
+use frame_system::{EnsureOneOf, EnsureRoot};
 
 use fp_rpc::TransactionStatus;
 use pallet_ethereum::{Call::transact, Transaction as EthereumTransaction};
-use pallet_evm::{Account as EVMAccount, EnsureAddressTruncated, Runner};
+use pallet_evm::{Account as EVMAccount, EVMCurrencyAdapter, EnsureAddressTruncated, Runner};
 use pallet_evm::FeeCalculator;
 use pallet_evm_account::EvmAddressMapping;
 use precompiles::FrontierPrecompiles;
-use impls::MergeAccountEvm;
+use impls::{
+	DealWithFees, FeelessCallFilter, FeelessWrapperFees, FeelessWrappers, FindAuthorAccountId,
+	FindAuthorEvmAddress, MergeAccountEvm, UnpausablePallets, WeightToFee,
+};
 
 mod precompiles;
 mod impls;
+mod migrations;
+#[cfg(test)]
+mod tests;
 mod weights;
 
 /// Import the template pallet.
@@ -124,10 +137,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 103,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -154,13 +167,14 @@ pub fn native_version() -> NativeVersion {
 }
 
 const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
+/// We allow for 2 seconds of compute with a 6 second average block time.
+const MAXIMUM_BLOCK_WEIGHT: Weight = 2 * WEIGHT_PER_SECOND;
 
 parameter_types! {
 	pub const Version: RuntimeVersion = VERSION;
 	pub const BlockHashCount: BlockNumber = 2400;
-	/// We allow for 2 seconds of compute with a 6 second average block time.
 	pub BlockWeights: frame_system::limits::BlockWeights = frame_system::limits::BlockWeights
-		::with_sensible_defaults(2 * WEIGHT_PER_SECOND, NORMAL_DISPATCH_RATIO);
+		::with_sensible_defaults(MAXIMUM_BLOCK_WEIGHT, NORMAL_DISPATCH_RATIO);
 	pub BlockLength: frame_system::limits::BlockLength = frame_system::limits::BlockLength
 		::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
 	pub const SS58Prefix: u8 = 42;
@@ -169,8 +183,9 @@ parameter_types! {
 // Configure FRAME pallets to include in runtime.
 
 impl frame_system::Config for Runtime {
-	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = frame_support::traits::Everything;
+	/// The basic call filter to use in dispatchable. Lets through every call that is not
+	/// paused by `TxPause`.
+	type BaseCallFilter = pallet_tx_pause::PausedCallFilter<Runtime>;
 	/// Block & extrinsics weights: base values and limits.
 	type BlockWeights = BlockWeights;
 	/// The maximum length of a block (in bytes).
@@ -225,17 +240,68 @@ parameter_types! {
 	pub const MaxAuthorities: u32 = 32;
 }
 
+parameter_types! {
+	pub const SessionPeriod: BlockNumber = HOURS;
+	pub const SessionOffset: BlockNumber = 0;
+	pub const DisabledValidatorsThreshold: Perbill = Perbill::from_percent(17);
+	pub const MinAuthorities: u32 = 1;
+}
+
+impl pallet_validator_set::Config for Runtime {
+	type Event = Event;
+	type AddRemoveOrigin = EnsureRootOrTwoThirdsCouncil;
+	type MinAuthorities = MinAuthorities;
+	type WeightInfo = weights::validator_set::WeightInfo<Runtime>;
+}
+
+impl pallet_session::Config for Runtime {
+	type Event = Event;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, ValidatorSet>;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type DisabledValidatorsThreshold = DisabledValidatorsThreshold;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_session::historical::Config for Runtime {
+	type FullIdentification = AccountId;
+	type FullIdentificationOf = ConvertInto;
+}
+
+impl pallet_offences::Config for Runtime {
+	type Event = Event;
+	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
+	type OnOffenceHandler = ValidatorSet;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = Call;
+}
+
 impl pallet_aura::Config for Runtime {
 	type AuthorityId = AuraId;
-	type DisabledValidators = ();
+	type DisabledValidators = Session;
 	type MaxAuthorities = MaxAuthorities;
 }
 
+parameter_types! {
+	/// Equivocation reports stay valid for a day worth of blocks.
+	pub const ReportLongevity: u64 = DAYS as u64;
+}
+
 impl pallet_grandpa::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
 
-	type KeyOwnerProofSystem = ();
+	type KeyOwnerProofSystem = Historical;
 
 	type KeyOwnerProof =
 		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
@@ -245,7 +311,8 @@ impl pallet_grandpa::Config for Runtime {
 		GrandpaId,
 	)>>::IdentificationTuple;
 
-	type HandleEquivocation = ();
+	type HandleEquivocation =
+		pallet_grandpa::EquivocationHandler<Self::KeyOwnerIdentification, Offences, ReportLongevity>;
 
 	type WeightInfo = ();
 	type MaxAuthorities = MaxAuthorities;
@@ -282,53 +349,428 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = ();
 }
 
+/// Base unit of BIC, which has 18 decimals.
+pub const BIC: Balance = 1_000_000_000_000_000_000;
+pub const MILLI_BIC: Balance = BIC / 1_000;
+pub const MICRO_BIC: Balance = MILLI_BIC / 1_000;
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = 100 * BIC;
+	pub const SpendPeriod: BlockNumber = 6 * DAYS;
+	pub const Burn: Permill = Permill::zero();
+	pub const MaxApprovals: u32 = 100;
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureRootOrThreeFifthsCouncil;
+	type RejectOrigin = EnsureRootOrHalfCouncil;
+	type Event = Event;
+	/// Slashed proposal bonds stay in the treasury.
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = MaxApprovals;
+}
+
 parameter_types! {
-	pub const TransactionByteFee: Balance = 1;
+	pub const TransactionByteFee: Balance = 10 * MICRO_BIC;
+	/// Fees rise while blocks are fuller than this, and fall while they are emptier.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000u128);
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = FeelessWrapperFees<CurrencyAdapter<Balances, DealWithFees>>;
 	type TransactionByteFee = TransactionByteFee;
-	type WeightToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ();
+	type WeightToFee = WeightToFee;
+	type FeeMultiplierUpdate =
+		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
 }
 
-impl pallet_sudo::Config for Runtime {
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+type CouncilCollective = pallet_collective::Instance1;
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// Root, or at least half of the council.
+pub type EnsureRootOrHalfCouncil = EnsureOneOf<
+	AccountId,
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, CouncilCollective>,
+>;
+
+/// Root, or at least three fifths of the council.
+pub type EnsureRootOrThreeFifthsCouncil = EnsureOneOf<
+	AccountId,
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<_3, _5, AccountId, CouncilCollective>,
+>;
+
+/// Root, or at least two thirds of the council.
+pub type EnsureRootOrTwoThirdsCouncil = EnsureOneOf<
+	AccountId,
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>,
+>;
+
+parameter_types! {
+	pub const TechnicalMotionDuration: BlockNumber = 3 * DAYS;
+	pub const TechnicalMaxProposals: u32 = 100;
+	pub const TechnicalMaxMembers: u32 = 100;
+}
+
+type TechnicalCollective = pallet_collective::Instance2;
+impl pallet_collective::Config<TechnicalCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
 	type Event = Event;
+	type MotionDuration = TechnicalMotionDuration;
+	type MaxProposals = TechnicalMaxProposals;
+	type MaxMembers = TechnicalMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// Root, or at least half of the technical committee.
+pub type EnsureRootOrHalfTechnicalCommittee = EnsureOneOf<
+	AccountId,
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, TechnicalCollective>,
+>;
+
+impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrTwoThirdsCouncil;
+	type RemoveOrigin = EnsureRootOrTwoThirdsCouncil;
+	type SwapOrigin = EnsureRootOrTwoThirdsCouncil;
+	type ResetOrigin = EnsureRootOrTwoThirdsCouncil;
+	type PrimeOrigin = EnsureRootOrTwoThirdsCouncil;
+	type MembershipInitialized = Council;
+	type MembershipChanged = Council;
+	type MaxMembers = CouncilMaxMembers;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_membership::Config<pallet_membership::Instance2> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrTwoThirdsCouncil;
+	type RemoveOrigin = EnsureRootOrTwoThirdsCouncil;
+	type SwapOrigin = EnsureRootOrTwoThirdsCouncil;
+	type ResetOrigin = EnsureRootOrTwoThirdsCouncil;
+	type PrimeOrigin = EnsureRootOrTwoThirdsCouncil;
+	type MembershipInitialized = TechnicalCommittee;
+	type MembershipChanged = TechnicalCommittee;
+	type MaxMembers = TechnicalMaxMembers;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MaxPauseNameLen: u32 = 64;
+}
+
+impl pallet_tx_pause::Config for Runtime {
+	type Event = Event;
+	/// Half of the technical committee can halt a pallet during an incident.
+	type PauseOrigin = EnsureRootOrHalfTechnicalCommittee;
+	type UnpauseOrigin = EnsureRootOrHalfTechnicalCommittee;
+	type UnpausablePallets = UnpausablePallets;
+	type KnownCalls = pallet_tx_pause::KnownCallsOf<Runtime>;
+	type MaxNameLen = MaxPauseNameLen;
+	type WeightInfo = weights::tx_pause::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
+	pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
 	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
 }
 
-frame_support::parameter_types! {
-	pub BoundDivision: U256 = U256::from(1024);
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 7 * DAYS;
+	pub const VotingPeriod: BlockNumber = 7 * DAYS;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * HOURS;
+	pub const InstantAllowed: bool = true;
+	pub const MinimumDeposit: Balance = 100 * BIC;
+	pub const EnactmentPeriod: BlockNumber = 2 * DAYS;
+	pub const CooloffPeriod: BlockNumber = 7 * DAYS;
+	pub const PreimageByteDeposit: Balance = BIC / 100;
+	pub const MaxVotes: u32 = 100;
+	pub const MaxProposals: u32 = 100;
 }
 
-impl pallet_dynamic_fee::Config for Runtime {
-	type MinGasPriceBoundDivisor = BoundDivision;
+impl pallet_democracy::Config for Runtime {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	/// A straight majority of the council can decide what their next motion is.
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, CouncilCollective>;
+	/// A super-majority can have the next scheduled referendum be a straight majority-carries vote.
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<_3, _5, AccountId, CouncilCollective>;
+	/// A unanimous council can have the next scheduled referendum be a straight default-carries
+	/// (NTB) vote.
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	/// Two thirds of the council can have an external proposal tabled immediately
+	/// for a fast-tracked referendum.
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	type InstantOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	type InstantAllowed = InstantAllowed;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	/// To cancel a proposal which has been passed, 2/3 of the council must agree to it.
+	type CancellationOrigin = EnsureRootOrTwoThirdsCouncil;
+	/// To cancel a proposal before it has been passed, the council must be unanimous or
+	/// Root must agree.
+	type CancelProposalOrigin = EnsureOneOf<
+		AccountId,
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>,
+	>;
+	type BlacklistOrigin = EnsureRoot<AccountId>;
+	/// Any single council member may veto a coming council proposal, however they can
+	/// only do it once and it lasts only for the cool-off period.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type Slash = Treasury;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+	type MaxProposals = MaxProposals;
 }
 
-pub struct FindAuthorTruncated<F>(PhantomData<F>);
-impl<F: FindAuthor<u32>> FindAuthor<H160> for FindAuthorTruncated<F> {
-	fn find_author<'a, I>(digests: I) -> Option<H160>
-		where
-			I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
-	{
-		if let Some(author_index) = F::find_author(digests) {
-			let authority_id = Aura::authorities()[author_index as usize].clone();
-			return Some(H160::from_slice(&authority_id.to_raw_vec()[4..24]));
+parameter_types! {
+	pub const MinVestedTransfer: Balance = BIC;
+}
+
+impl pallet_vesting::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
+	pub const MultisigDepositBase: Balance = BIC + 88 * BIC / 100;
+	// Additional storage item size of 32 bytes.
+	pub const MultisigDepositFactor: Balance = 32 * BIC / 100;
+	pub const MaxSignatories: u16 = 100;
+}
+
+impl pallet_multisig::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type DepositBase = MultisigDepositBase;
+	type DepositFactor = MultisigDepositFactor;
+	type MaxSignatories = MaxSignatories;
+	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = BIC + 8 * BIC / 100;
+	// Additional storage item size of 33 bytes.
+	pub const ProxyDepositFactor: Balance = 33 * BIC / 100;
+	pub const MaxProxies: u16 = 32;
+	pub const AnnouncementDepositBase: Balance = BIC + 8 * BIC / 100;
+	pub const AnnouncementDepositFactor: Balance = 66 * BIC / 100;
+	pub const MaxPending: u16 = 32;
+}
+
+/// The kinds of calls an account can let a proxy make on its behalf.
+#[derive(
+	Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub enum ProxyType {
+	/// Every call.
+	Any,
+	/// Every call that cannot move funds.
+	NonTransfer,
+	/// Treasury and governance calls.
+	Governance,
+	/// Staking for bandwidth, without access to the staked funds.
+	Feeless,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => !matches!(
+				c,
+				Call::Balances(..) |
+					Call::Vesting(pallet_vesting::Call::vested_transfer { .. }) |
+					Call::EVM(..) |
+					Call::Feeless(pallet_feeless::Call::feeless_call { .. }) |
+					Call::EvmAccounts(pallet_evm_account::Call::claim_account { .. }) |
+					Call::SocialToken(..) |
+					Call::Nft(..) |
+					Call::AuctionHouse(..) |
+					Call::Tips(..)
+			),
+			ProxyType::Governance => matches!(
+				c,
+				Call::Treasury(..) |
+					Call::Council(..) |
+					Call::CouncilMembership(..) |
+					Call::Democracy(..) |
+					Call::Utility(..)
+			),
+			ProxyType::Feeless => matches!(
+				c,
+				Call::Feeless(pallet_feeless::Call::stake_bic { .. }) |
+					Call::Feeless(pallet_feeless::Call::unstake_bic { .. }) |
+					Call::Feeless(pallet_feeless::Call::claim_rewards { .. })
+			),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(ProxyType::NonTransfer, ProxyType::Governance) => true,
+			(ProxyType::NonTransfer, ProxyType::Feeless) => true,
+			_ => false,
 		}
-		None
+	}
+}
+
+impl pallet_proxy::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+parameter_types! {
+	pub const BasicDeposit: Balance = 10 * BIC;
+	pub const FieldDeposit: Balance = BIC;
+	pub const SubAccountDeposit: Balance = 2 * BIC;
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+}
+
+impl pallet_identity::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasicDeposit = BasicDeposit;
+	type FieldDeposit = FieldDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxAdditionalFields = MaxAdditionalFields;
+	type MaxRegistrars = MaxRegistrars;
+	type Slashed = Treasury;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type RegistrarOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
+/// Gas the EVM executes per second of block time on reference hardware.
+pub const GAS_PER_SECOND: u64 = 40_000_000;
+
+/// Weight of one unit of gas.
+pub const WEIGHT_PER_GAS: u64 = WEIGHT_PER_SECOND / GAS_PER_SECOND;
+
+/// Converts between EVM gas and weight at `WEIGHT_PER_GAS`, so that gas is paid for with
+/// the same block time as any other extrinsic.
+pub struct BeinGasWeightMapping;
+impl pallet_evm::GasWeightMapping for BeinGasWeightMapping {
+	fn gas_to_weight(gas: u64) -> Weight {
+		gas.saturating_mul(WEIGHT_PER_GAS)
+	}
+
+	fn weight_to_gas(weight: Weight) -> u64 {
+		weight / WEIGHT_PER_GAS
 	}
 }
 
 parameter_types! {
-	pub const ChainId: u64 = 111121;
-	pub BlockGasLimit: U256 = U256::from(u32::max_value());
+	/// Gas of the largest EVM transaction: the weight of the largest normal extrinsic, less
+	/// the base weight every extrinsic is charged.
+	pub BlockGasLimit: U256 = U256::from(
+		BlockWeights::get()
+			.get(frame_support::weights::DispatchClass::Normal)
+			.max_extrinsic
+			.unwrap_or(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT)
+			.saturating_sub(ExtrinsicBaseWeight::get()) / WEIGHT_PER_GAS
+	);
 	pub PrecompilesValue: FrontierPrecompiles<Runtime> = FrontierPrecompiles::<_>::new();
 }
 
+impl pallet_evm_chain_id::Config for Runtime {}
+
 impl pallet_evm::Config for Runtime {
 	type FeeCalculator = BaseFee;
-	type GasWeightMapping = ();
+	type GasWeightMapping = BeinGasWeightMapping;
 	type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
 	type CallOrigin = EnsureAddressTruncated;
 	type WithdrawOrigin = EnsureAddressTruncated;
@@ -337,11 +779,11 @@ impl pallet_evm::Config for Runtime {
 	type Event = Event;
 	type PrecompilesType = FrontierPrecompiles<Self>;
 	type PrecompilesValue = PrecompilesValue;
-	type ChainId = ChainId;
+	type ChainId = EVMChainId;
 	type BlockGasLimit = BlockGasLimit;
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
-	type OnChargeTransaction = ();
-	type FindAuthor = FindAuthorTruncated<Aura>;
+	type OnChargeTransaction = EVMCurrencyAdapter<Balances, DealWithFees>;
+	type FindAuthor = FindAuthorEvmAddress<FindAuthorAccountId>;
 }
 
 impl pallet_ethereum::Config for Runtime {
@@ -351,6 +793,18 @@ impl pallet_ethereum::Config for Runtime {
 
 parameter_types! {
 	pub const Period: BlockNumber = DAYS;
+	/// Spent bandwidth comes back over a day rather than all at once at the next period.
+	pub const FeelessRegenerationWindow: BlockNumber = DAYS;
+	pub const MaxFeelessCallsPerBlock: u32 = 5;
+	/// Fee-free calls can take up to a third of the weight of normal calls in a block.
+	pub MaxFeelessWeightPerBlock: Weight = NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT / 3;
+	/// Staking rewards are paid from this account, funded by treasury spends.
+	pub const FeelessPalletId: PalletId = PalletId(*b"py/fless");
+	/// No staking rewards until governance sets them.
+	pub const FeelessRewardPerPeriod: Balance = 0;
+	pub const MintFeelessRewards: bool = false;
+	pub const UnusedBandwidthBonus: Perbill = Perbill::zero();
+	pub const MaxFeelessRefillsPerBlock: u32 = 500;
 }
 
 /// Configure the pallet-template in pallets/template.
@@ -364,15 +818,56 @@ impl pallet_feeless::Config for Runtime {
 	type Currency = Balances;
 
 	type TxPayment = TransactionPayment;
+
+	type ForceOrigin = EnsureRootOrTwoThirdsCouncil;
+
+	type CallFilter = FeelessCallFilter;
+
+	type NestedFeelessCalls = FeelessWrappers;
+
+	// Bandwidth by staking level. `pallet_feeless::ProportionalBandwidth<Budget>` shares a
+	// per-period budget by stake instead.
+	type BandwidthCurve = pallet_feeless::LevelBandwidth;
+
+	type RegenerationWindow = FeelessRegenerationWindow;
+
+	type MaxFeelessCallsPerBlock = MaxFeelessCallsPerBlock;
+
+	type MaxFeelessWeightPerBlock = MaxFeelessWeightPerBlock;
+
+	type OnFeeCharged = DealWithFees;
+
+	type PalletId = FeelessPalletId;
+
+	type RewardPerPeriod = FeelessRewardPerPeriod;
+
+	type MintRewards = MintFeelessRewards;
+
+	type UnusedBandwidthBonus = UnusedBandwidthBonus;
+
+	type MaxRefillsPerBlock = MaxFeelessRefillsPerBlock;
+
+	type WeightInfo = weights::feeless::WeightInfo<Runtime>;
 }
 
+impl pallet_base_fee_target::Config for Runtime {
+	type Event = Event;
+	type SetOrigin = EnsureRootOrTwoThirdsCouncil;
+	type WeightInfo = weights::base_fee_target::WeightInfo<Runtime>;
+}
+
+/// `pallet_base_fee` keeps the base fee unchanged halfway between `lower` and `upper`, and
+/// moves it by the whole elasticity at either bound. Bounds of zero and twice the target put
+/// the midpoint on the target fullness of `BaseFeeTarget`, so a full-to-target block behaves
+/// like in Ethereum.
 pub struct BaseFeeThreshold;
 impl pallet_base_fee::BaseFeeThreshold for BaseFeeThreshold {
 	fn lower() -> Permill {
 		Permill::zero()
 	}
 	fn upper() -> Permill {
-		Permill::from_parts(1_000_000)
+		let target = BaseFeeTarget::target_fullness();
+		target.saturating_add(target)
 	}
 }
 
@@ -381,6 +876,100 @@ impl pallet_base_fee::Config for Runtime {
 	type Threshold = BaseFeeThreshold;
 }
 
+parameter_types! {
+	pub const TokenDeposit: Balance = 100 * BIC;
+	pub const TokenStringLimit: u32 = 50;
+	pub const TokenRemoveItemsLimit: u32 = 500;
+}
+
+impl pallet_social_token::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type TokenDeposit = TokenDeposit;
+	type StringLimit = TokenStringLimit;
+	type RemoveItemsLimit = TokenRemoveItemsLimit;
+	type WeightInfo = weights::social_token::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const CollectionDeposit: Balance = 100 * BIC;
+	pub const MaxRoyalty: Perbill = Perbill::from_percent(25);
+	pub const NftStringLimit: u32 = 256;
+	pub const NftRemoveItemsLimit: u32 = 500;
+}
+
+impl pallet_bein_nft::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type CollectionDeposit = CollectionDeposit;
+	type MaxRoyalty = MaxRoyalty;
+	type StringLimit = NftStringLimit;
+	type RemoveItemsLimit = NftRemoveItemsLimit;
+	type WeightInfo = weights::bein_nft::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const MinAuctionDuration: BlockNumber = 10 * MINUTES;
+	pub const MaxAuctionDuration: BlockNumber = 30 * DAYS;
+	pub const AntiSnipingPeriod: BlockNumber = 5 * MINUTES;
+	pub const MinBidIncrement: Perbill = Perbill::from_percent(5);
+	pub const MaxSettlementsPerBlock: u32 = 20;
+}
+
+impl pallet_auction_house::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Nfts = Nft;
+	type MinDuration = MinAuctionDuration;
+	type MaxDuration = MaxAuctionDuration;
+	type AntiSnipingPeriod = AntiSnipingPeriod;
+	type MinBidIncrement = MinBidIncrement;
+	type MaxSettlementsPerBlock = MaxSettlementsPerBlock;
+	type WeightInfo = weights::auction_house::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const MinTip: Balance = BIC / 100;
+	pub const MaxMemoLength: u32 = 140;
+	pub const TipsPeriod: BlockNumber = DAYS;
+	pub const LeaderboardSize: u32 = 20;
+	pub const TipsStatsHistory: u32 = 90;
+	pub const TipsMaxPrunedPerBlock: u32 = 500;
+}
+
+impl pallet_tips::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type MinTip = MinTip;
+	type MaxMemoLength = MaxMemoLength;
+	type Period = TipsPeriod;
+	type LeaderboardSize = LeaderboardSize;
+	type StatsHistory = TipsStatsHistory;
+	type MaxPrunedPerBlock = TipsMaxPrunedPerBlock;
+	type WeightInfo = weights::tips::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const GroupDeposit: Balance = 10 * BIC;
+	pub const MaxGroupAdmins: u32 = 20;
+	pub const MaxGroupMembers: u32 = 1_000;
+	pub const MaxGroupExtensions: u32 = 50;
+	pub const MaxGroupInvitations: u32 = 100;
+	pub const ExtensionStringLimit: u32 = 64;
+}
+
+impl pallet_groups::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type GroupDeposit = GroupDeposit;
+	type MaxAdmins = MaxGroupAdmins;
+	type MaxMembers = MaxGroupMembers;
+	type MaxExtensions = MaxGroupExtensions;
+	type MaxInvitations = MaxGroupInvitations;
+	type StringLimit = ExtensionStringLimit;
+	type WeightInfo = weights::groups::WeightInfo<Runtime>;
+}
+
 impl pallet_evm_account::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
@@ -399,20 +988,42 @@ construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		ValidatorSet: pallet_validator_set::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Session: pallet_session::{Pallet, Call, Storage, Config<T>, Event},
+		Historical: pallet_session_historical::{Pallet},
+		Offences: pallet_offences::{Pallet, Storage, Event},
 		Aura: pallet_aura::{Pallet, Config<T>},
-		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event},
+		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event, ValidateUnsigned},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>, Config<T>},
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
-		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>},
+		Council: pallet_collective::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>},
+		CouncilMembership: pallet_membership::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>},
+		TechnicalCommittee: pallet_collective::<Instance2>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>},
+		TechnicalMembership: pallet_membership::<Instance2>::{Pallet, Call, Storage, Event<T>, Config<T>},
+		TxPause: pallet_tx_pause::{Pallet, Call, Storage, Event},
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
+		Democracy: pallet_democracy::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Identity: pallet_identity::{Pallet, Call, Storage, Event<T>},
+		Utility: pallet_utility::{Pallet, Call, Event},
+		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>},
+		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
 
 		Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Config, Origin},
 		EVM: pallet_evm::{Pallet, Config, Call, Storage, Event<T>},
-		DynamicFee: pallet_dynamic_fee::{Pallet, Call, Storage, Config, Inherent},
+		EVMChainId: pallet_evm_chain_id::{Pallet, Storage, Config},
 		BaseFee: pallet_base_fee::{Pallet, Call, Storage, Config<T>, Event},
+		BaseFeeTarget: pallet_base_fee_target::{Pallet, Call, Storage, Config, Event},
 
 		// Include the custom logic from the pallet-template in the runtime.
 		Feeless: pallet_feeless::{Pallet, Call, Config<T>, Storage, Event<T>},
 		EvmAccounts: pallet_evm_account::{Pallet, Call, Storage, Event<T>},
+		SocialToken: pallet_social_token::{Pallet, Call, Storage, Event<T>},
+		Nft: pallet_bein_nft::{Pallet, Call, Storage, Event<T>},
+		AuctionHouse: pallet_auction_house::{Pallet, Call, Storage, Event<T>},
+		Tips: pallet_tips::{Pallet, Call, Storage, Event<T>},
+		Groups: pallet_groups::{Pallet, Call, Storage, Event<T>},
 
 	}
 );
@@ -460,6 +1071,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_feeless::CheckFeeless<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = fp_self_contained::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -472,6 +1084,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
+	migrations::Migrations,
 >;
 
 impl fp_self_contained::SelfContainedCall for Call {
@@ -493,6 +1106,10 @@ impl fp_self_contained::SelfContainedCall for Call {
 
 	fn validate_self_contained(&self, info: &Self::SignedInfo) -> Option<TransactionValidity> {
 		match self {
+			// Keep paused Ethereum transactions out of the pool rather than failing them in blocks.
+			Call::Ethereum(_) if !<Runtime as frame_system::Config>::BaseCallFilter::contains(self) => {
+				Some(Err(InvalidTransaction::Call.into()))
+			}
 			Call::Ethereum(call) => call.validate_self_contained(info),
 			_ => None,
 		}
@@ -503,6 +1120,9 @@ impl fp_self_contained::SelfContainedCall for Call {
 		info: &Self::SignedInfo,
 	) -> Option<Result<(), TransactionValidityError>> {
 		match self {
+			Call::Ethereum(_) if !<Runtime as frame_system::Config>::BaseCallFilter::contains(self) => {
+				Some(Err(InvalidTransaction::Call.into()))
+			}
 			Call::Ethereum(call) => call.pre_dispatch_self_contained(info),
 			_ => None,
 		}
@@ -612,23 +1232,27 @@ impl_runtime_apis! {
 		}
 
 		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: fg_primitives::EquivocationProof<
+			equivocation_proof: fg_primitives::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
+			key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(
+				equivocation_proof,
+				key_owner_proof,
+			)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: fg_primitives::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			Historical::prove((fg_primitives::KEY_TYPE, authority_id))
+				.map(|p| p.encode())
+				.map(fg_primitives::OpaqueKeyOwnershipProof::new)
 		}
 	}
 
@@ -779,6 +1403,20 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString> {
+			// Runs `pre_upgrade` and `post_upgrade` of every pallet and migration around the
+			// upgrade, so a broken migration fails here instead of on chain.
+			let weight = Executive::try_runtime_upgrade()?;
+			Ok((weight, BlockWeights::get().max_block))
+		}
+
+		fn execute_block_no_check(block: Block) -> Weight {
+			Executive::execute_block_no_check(block)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -794,7 +1432,16 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, frame_system, SystemBench::<Runtime>);
 			list_benchmark!(list, extra, pallet_balances, Balances);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
-			list_benchmark!(list, extra, pallet_template, TemplateModule);
+			list_benchmark!(list, extra, pallet_treasury, Treasury);
+			list_benchmark!(list, extra, pallet_collective, Council);
+			list_benchmark!(list, extra, pallet_democracy, Democracy);
+			list_benchmark!(list, extra, pallet_scheduler, Scheduler);
+			list_benchmark!(list, extra, pallet_identity, Identity);
+			list_benchmark!(list, extra, pallet_vesting, Vesting);
+			list_benchmark!(list, extra, pallet_utility, Utility);
+			list_benchmark!(list, extra, pallet_multisig, Multisig);
+			list_benchmark!(list, extra, pallet_proxy, Proxy);
+			list_benchmark!(list, extra, pallet_feeless, Feeless);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -828,7 +1475,16 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
-			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, pallet_treasury, Treasury);
+			add_benchmark!(params, batches, pallet_collective, Council);
+			add_benchmark!(params, batches, pallet_democracy, Democracy);
+			add_benchmark!(params, batches, pallet_scheduler, Scheduler);
+			add_benchmark!(params, batches, pallet_identity, Identity);
+			add_benchmark!(params, batches, pallet_vesting, Vesting);
+			add_benchmark!(params, batches, pallet_utility, Utility);
+			add_benchmark!(params, batches, pallet_multisig, Multisig);
+			add_benchmark!(params, batches, pallet_proxy, Proxy);
+			add_benchmark!(params, batches, pallet_feeless, Feeless);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -0,0 +1,261 @@
+//! Storage migrations run by `Executive` on runtime upgrade.
+
+#[cfg(feature = "try-runtime")]
+use frame_support::ensure;
+use frame_support::{
+	sp_io::hashing::twox_128,
+	storage::{
+		migration::{get_storage_value, have_storage_value, remove_storage_prefix},
+		unhashed,
+	},
+	traits::{ChangeMembers, Get, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use pallet_session::SessionManager;
+use sp_core::sr25519;
+use sp_runtime::{traits::OpaqueKeys, Permill};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+use crate::{opaque::SessionKeys, AccountId, Aura, Grandpa, Runtime, Session, System};
+
+/// All migrations to run on the next runtime upgrade, in order.
+///
+/// They are one-shot migrations for the upgrade to spec version 103. Each checks for the
+/// storage it migrates and only reads it once that is done, but they should still be dropped
+/// from this tuple, and their code removed, with the next spec version bump.
+pub type Migrations = (
+	SeedMembershipFromSudo<Runtime, pallet_membership::Instance1>,
+	SeedMembershipFromSudo<Runtime, pallet_membership::Instance2>,
+	RemoveSudo<Runtime>,
+	SeedSessionFromAuthorities,
+	SetEvmChainId<Runtime>,
+	RemoveDynamicFee<Runtime>,
+	MoveBaseFeeTargetFullness<Runtime>,
+);
+
+/// Makes the sudo key the sole member of an empty membership instance, and so of the
+/// collective it manages, before `RemoveSudo` deletes the key. Without it root could only be
+/// reached through democracy, with no council to propose or fast-track anything.
+pub struct SeedMembershipFromSudo<T, I>(PhantomData<(T, I)>);
+impl<T: pallet_membership::Config<I>, I: 'static> OnRuntimeUpgrade
+	for SeedMembershipFromSudo<T, I>
+{
+	fn on_runtime_upgrade() -> Weight {
+		if !pallet_membership::Members::<T, I>::get().is_empty() {
+			return T::DbWeight::get().reads(1)
+		}
+		let key = match get_storage_value::<T::AccountId>(b"Sudo", b"Key", &[]) {
+			Some(key) => key,
+			None => return T::DbWeight::get().reads(2),
+		};
+
+		let members = sp_std::vec![key];
+		pallet_membership::Members::<T, I>::put(&members);
+		T::MembershipChanged::set_members_sorted(&members, &[]);
+		T::DbWeight::get().reads_writes(2, 3)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		ensure!(
+			!pallet_membership::Members::<T, I>::get().is_empty() ||
+				have_storage_value(b"Sudo", b"Key", &[]),
+			"no members and no sudo key to seed them from"
+		);
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		ensure!(!pallet_membership::Members::<T, I>::get().is_empty(), "membership left empty");
+		Ok(())
+	}
+}
+
+/// Removes the storage left behind by `pallet_sudo` once governance has taken over root.
+pub struct RemoveSudo<T>(PhantomData<T>);
+impl<T: frame_system::Config> OnRuntimeUpgrade for RemoveSudo<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if !have_storage_value(b"Sudo", b"Key", &[]) {
+			return T::DbWeight::get().reads(1)
+		}
+		remove_storage_prefix(b"Sudo", b"Key", &[]);
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		ensure!(!have_storage_value(b"Sudo", b"Key", &[]), "sudo key left behind");
+		Ok(())
+	}
+}
+
+/// Aura and Grandpa authorities used to be fixed at genesis. Hands them over to
+/// `pallet_session` and `pallet_validator_set` as they would have been set up at genesis, so
+/// the first session rotation keeps them and offences can be proven against their keys.
+///
+/// Each validator gets the account block authors were paid into so far: the one behind its
+/// sr25519 Aura key.
+pub struct SeedSessionFromAuthorities;
+impl SeedSessionFromAuthorities {
+	/// Validator accounts and session keys of the current authorities, Aura and Grandpa keys
+	/// paired in order. Empty if the two sets differ in size.
+	fn authorities() -> Vec<(AccountId, SessionKeys)> {
+		let aura = Aura::authorities();
+		let grandpa = Grandpa::grandpa_authorities();
+		if aura.len() != grandpa.len() {
+			return Vec::new()
+		}
+		aura.iter()
+			.cloned()
+			.zip(grandpa)
+			.map(|(aura, (grandpa, _))| {
+				let account = AccountId::from(sr25519::Public::from(aura.clone()));
+				(account, SessionKeys { aura, grandpa })
+			})
+			.collect()
+	}
+}
+
+impl OnRuntimeUpgrade for SeedSessionFromAuthorities {
+	fn on_runtime_upgrade() -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		if !Session::validators().is_empty() {
+			return db_weight.reads(1)
+		}
+		let authorities = Self::authorities();
+		if authorities.is_empty() {
+			return db_weight.reads(3)
+		}
+
+		for (account, keys) in &authorities {
+			for id in SessionKeys::key_ids() {
+				let key = (*id, keys.get_raw(*id).to_vec());
+				pallet_session::KeyOwner::<Runtime>::insert(key, account);
+			}
+			pallet_session::NextKeys::<Runtime>::insert(account, keys);
+			if System::inc_consumers(account).is_err() {
+				System::inc_providers(account);
+			}
+		}
+		let validators: Vec<AccountId> =
+			authorities.iter().map(|(account, _)| account.clone()).collect();
+		pallet_validator_set::Validators::<Runtime>::put(&validators);
+
+		// Genesis notes sessions 0 and 1 with `historical`; note the current and queued ones.
+		type Manager = <Runtime as pallet_session::Config>::SessionManager;
+		let index = Session::current_index();
+		Manager::new_session_genesis(index);
+		Manager::new_session_genesis(index + 1);
+		pallet_session::Validators::<Runtime>::put(&validators);
+		pallet_session::QueuedKeys::<Runtime>::put(&authorities);
+
+		let count = authorities.len() as Weight;
+		db_weight.reads_writes(8 + count, 7 + 4 * count)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		ensure!(
+			!Session::validators().is_empty() || !Self::authorities().is_empty(),
+			"no validators and no matching Aura and Grandpa authorities to seed them from"
+		);
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		let validators = Session::validators();
+		ensure!(!validators.is_empty(), "session validators left empty");
+		ensure!(
+			pallet_validator_set::Validators::<Runtime>::get() == validators,
+			"validator set differs from the session validators"
+		);
+		let keys: Vec<_> =
+			validators.iter().map(|v| pallet_session::NextKeys::<Runtime>::get(v)).collect();
+		ensure!(
+			Self::authorities().into_iter().map(|(_, keys)| Some(keys)).eq(keys),
+			"session keys differ from the Aura and Grandpa authorities"
+		);
+		Ok(())
+	}
+}
+
+/// The EVM chain id used to be a runtime constant. Chains started before it moved to
+/// `pallet_evm_chain_id` keep that id instead of falling back to zero.
+pub struct SetEvmChainId<T>(PhantomData<T>);
+impl<T: pallet_evm_chain_id::Config> OnRuntimeUpgrade for SetEvmChainId<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if !pallet_evm_chain_id::ChainId::<T>::exists() {
+			pallet_evm_chain_id::ChainId::<T>::put(LEGACY_CHAIN_ID);
+			return T::DbWeight::get().reads_writes(1, 1)
+		}
+		T::DbWeight::get().reads(1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		ensure!(pallet_evm_chain_id::ChainId::<T>::get() != 0, "EVM chain id not set");
+		Ok(())
+	}
+}
+
+/// Chain id hard-coded in the runtime before `SetEvmChainId`.
+const LEGACY_CHAIN_ID: u64 = 111121;
+
+/// Removes the storage of `pallet_dynamic_fee`, whose author-voted minimum gas price gave way
+/// to the EIP-1559 base fee of `pallet_base_fee`.
+pub struct RemoveDynamicFee<T>(PhantomData<T>);
+impl<T: frame_system::Config> OnRuntimeUpgrade for RemoveDynamicFee<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if !have_storage_value(b"DynamicFee", b"MinGasPrice", &[]) &&
+			!have_storage_value(b"DynamicFee", b"TargetMinGasPrice", &[])
+		{
+			return T::DbWeight::get().reads(2)
+		}
+		remove_storage_prefix(b"DynamicFee", b"MinGasPrice", &[]);
+		remove_storage_prefix(b"DynamicFee", b"TargetMinGasPrice", &[]);
+		T::DbWeight::get().reads_writes(2, 2)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		ensure!(
+			!have_storage_value(b"DynamicFee", b"MinGasPrice", &[]) &&
+				!have_storage_value(b"DynamicFee", b"TargetMinGasPrice", &[]),
+			"dynamic fee storage left behind"
+		);
+		Ok(())
+	}
+}
+
+/// The base fee target fullness used to be a `parameter_types!` storage item, which root
+/// could only change with `System::set_storage`. Moves a target root set that way into
+/// `pallet_base_fee_target`.
+pub struct MoveBaseFeeTargetFullness<T>(PhantomData<T>);
+impl<T: pallet_base_fee_target::Config> OnRuntimeUpgrade for MoveBaseFeeTargetFullness<T> {
+	fn on_runtime_upgrade() -> Weight {
+		match unhashed::take::<Permill>(&legacy_target_fullness_key()) {
+			Some(target) if pallet_base_fee_target::Pallet::<T>::is_valid(target) => {
+				pallet_base_fee_target::TargetFullness::<T>::put(target);
+				T::DbWeight::get().reads_writes(1, 2)
+			}
+			Some(_) => T::DbWeight::get().reads_writes(1, 1),
+			None => T::DbWeight::get().reads(1),
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		ensure!(
+			!unhashed::exists(&legacy_target_fullness_key()),
+			"legacy base fee target fullness left behind"
+		);
+		Ok(())
+	}
+}
+
+/// Where `parameter_types!` kept `BaseFeeTargetFullness` before `MoveBaseFeeTargetFullness`.
+fn legacy_target_fullness_key() -> [u8; 16] {
+	twox_128(b":BaseFeeTargetFullness:")
+}
@@ -0,0 +1,178 @@
+//! ERC-20 view of the social tokens of `pallet_social_token`.
+
+use core::convert::TryFrom;
+use pallet_evm::{AddressMapping, Context, PrecompileResult};
+use pallet_social_token::{BalanceOf, Pallet as SocialToken, TokenId};
+use sp_core::{H160, H256, U256};
+use sp_std::{marker::PhantomData, vec};
+
+use super::utils::{
+	address_to_h256, check_called_at, check_not_paused, check_not_static, dispatch_revert, error,
+	log3, EvmDataReader, EvmDataWriter, Gasometer,
+};
+
+/// `Transfer(address,address,uint256)`
+pub const SELECTOR_LOG_TRANSFER: [u8; 32] =
+	hex_literal::hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+/// `Approval(address,address,uint256)`
+pub const SELECTOR_LOG_APPROVAL: [u8; 32] =
+	hex_literal::hex!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+
+/// Function selectors of the ERC-20 interface.
+mod action {
+	pub const TOTAL_SUPPLY: u32 = 0x18160ddd;
+	pub const BALANCE_OF: u32 = 0x70a08231;
+	pub const ALLOWANCE: u32 = 0xdd62ed3e;
+	pub const TRANSFER: u32 = 0xa9059cbb;
+	pub const APPROVE: u32 = 0x095ea7b3;
+	pub const TRANSFER_FROM: u32 = 0x23b872dd;
+	pub const NAME: u32 = 0x06fdde03;
+	pub const SYMBOL: u32 = 0x95d89b41;
+	pub const DECIMALS: u32 = 0x313ce567;
+}
+
+pub struct Erc20Precompile<R>(PhantomData<R>);
+
+impl<R> Erc20Precompile<R>
+where
+	R: pallet_social_token::Config + pallet_tx_pause::Config,
+	BalanceOf<R>: TryFrom<U256> + Into<U256>,
+{
+	pub fn execute(
+		id: TokenId,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		let mut gasometer = Gasometer::new(target_gas);
+		let mut reader = EvmDataReader::new(input);
+		let address = pallet_social_token::Pallet::<R>::token_address(id);
+
+		match reader.read_selector()? {
+			action::TOTAL_SUPPLY => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let supply: U256 = pallet_social_token::Pallet::<R>::total_supply(id).into();
+				Ok(gasometer.succeed(EvmDataWriter::new().write_u256(supply).build(), vec![]))
+			}
+			action::BALANCE_OF => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let who = R::AddressMapping::into_account_id(reader.read_address()?);
+				let balance: U256 = pallet_social_token::Pallet::<R>::balance_of(id, who).into();
+				Ok(gasometer.succeed(EvmDataWriter::new().write_u256(balance).build(), vec![]))
+			}
+			action::ALLOWANCE => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let owner = R::AddressMapping::into_account_id(reader.read_address()?);
+				let spender = R::AddressMapping::into_account_id(reader.read_address()?);
+				let allowance: U256 =
+					pallet_social_token::Pallet::<R>::allowance(id, (owner, spender)).into();
+				Ok(gasometer.succeed(EvmDataWriter::new().write_u256(allowance).build(), vec![]))
+			}
+			action::TRANSFER => {
+				check_not_static(is_static)?;
+				check_called_at(context, address)?;
+				check_not_paused::<R, SocialToken<R>>(&mut gasometer, "transfer")?;
+				gasometer.record_reads_writes::<R>(3, 2)?;
+				let to = reader.read_address()?;
+				let value = reader.read_u256()?;
+
+				pallet_social_token::Pallet::<R>::do_transfer(
+					id,
+					&R::AddressMapping::into_account_id(context.caller),
+					&R::AddressMapping::into_account_id(to),
+					Self::balance(value)?,
+				)
+				.map_err(dispatch_revert)?;
+
+				Ok(gasometer.succeed(
+					EvmDataWriter::new().write_bool(true).build(),
+					vec![Self::transfer_log(address, context.caller, to, value)],
+				))
+			}
+			action::APPROVE => {
+				check_not_static(is_static)?;
+				check_called_at(context, address)?;
+				check_not_paused::<R, SocialToken<R>>(&mut gasometer, "approve")?;
+				gasometer.record_reads_writes::<R>(1, 1)?;
+				let spender = reader.read_address()?;
+				let value = reader.read_u256()?;
+
+				pallet_social_token::Pallet::<R>::do_approve(
+					id,
+					&R::AddressMapping::into_account_id(context.caller),
+					&R::AddressMapping::into_account_id(spender),
+					Self::balance(value)?,
+				)
+				.map_err(dispatch_revert)?;
+
+				Ok(gasometer.succeed(
+					EvmDataWriter::new().write_bool(true).build(),
+					vec![log3(
+						address,
+						H256::from(SELECTOR_LOG_APPROVAL),
+						address_to_h256(context.caller),
+						address_to_h256(spender),
+						EvmDataWriter::new().write_u256(value).build(),
+					)],
+				))
+			}
+			action::TRANSFER_FROM => {
+				check_not_static(is_static)?;
+				check_called_at(context, address)?;
+				check_not_paused::<R, SocialToken<R>>(&mut gasometer, "transfer_from")?;
+				gasometer.record_reads_writes::<R>(4, 3)?;
+				let from = reader.read_address()?;
+				let to = reader.read_address()?;
+				let value = reader.read_u256()?;
+
+				pallet_social_token::Pallet::<R>::do_transfer_from(
+					id,
+					&R::AddressMapping::into_account_id(context.caller),
+					&R::AddressMapping::into_account_id(from),
+					&R::AddressMapping::into_account_id(to),
+					Self::balance(value)?,
+				)
+				.map_err(dispatch_revert)?;
+
+				Ok(gasometer.succeed(
+					EvmDataWriter::new().write_bool(true).build(),
+					vec![Self::transfer_log(address, from, to, value)],
+				))
+			}
+			action::NAME => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let metadata = pallet_social_token::Pallet::<R>::metadata(id);
+				Ok(gasometer.succeed(EvmDataWriter::new().write_bytes(&metadata.name).build(), vec![]))
+			}
+			action::SYMBOL => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let metadata = pallet_social_token::Pallet::<R>::metadata(id);
+				Ok(gasometer.succeed(EvmDataWriter::new().write_bytes(&metadata.symbol).build(), vec![]))
+			}
+			action::DECIMALS => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let metadata = pallet_social_token::Pallet::<R>::metadata(id);
+				Ok(gasometer.succeed(
+					EvmDataWriter::new().write_u256(U256::from(metadata.decimals)).build(),
+					vec![],
+				))
+			}
+			_ => Err(error("unknown selector")),
+		}
+	}
+
+	fn balance(value: U256) -> Result<BalanceOf<R>, pallet_evm::PrecompileFailure> {
+		BalanceOf::<R>::try_from(value).map_err(|_| error("value out of bounds"))
+	}
+
+	fn transfer_log(address: H160, from: H160, to: H160, value: U256) -> pallet_evm::Log {
+		log3(
+			address,
+			H256::from(SELECTOR_LOG_TRANSFER),
+			address_to_h256(from),
+			address_to_h256(to),
+			EvmDataWriter::new().write_u256(value).build(),
+		)
+	}
+}
@@ -0,0 +1,268 @@
+//! ERC-721 view of the NFT collections of `pallet_bein_nft`.
+
+use pallet_bein_nft::{CollectionId, NonFungibles, Pallet as Nft};
+use pallet_evm::{AddressMapping, Context, PrecompileResult};
+use sp_core::{crypto::AccountId32, H160, H256, U256};
+use sp_runtime::traits::IsType;
+use sp_std::{marker::PhantomData, vec};
+
+use super::utils::{
+	address_to_h256, check_called_at, check_not_paused, check_not_static, dispatch_revert, error,
+	log3, log4, revert, u256_to_h256, EvmDataReader, EvmDataWriter, Gasometer,
+};
+
+/// `Transfer(address,address,uint256)`
+pub const SELECTOR_LOG_TRANSFER: [u8; 32] =
+	hex_literal::hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+/// `Approval(address,address,uint256)`
+pub const SELECTOR_LOG_APPROVAL: [u8; 32] =
+	hex_literal::hex!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+/// `ApprovalForAll(address,address,bool)`
+pub const SELECTOR_LOG_APPROVAL_FOR_ALL: [u8; 32] =
+	hex_literal::hex!("17307eab39ab6107e8899845ad3d59bd9653f200f220920489ca2b5937696c31");
+
+/// Function selectors of the ERC-721, ERC-721 metadata, ERC-165 and EIP-2981 interfaces.
+mod action {
+	pub const BALANCE_OF: u32 = 0x70a08231;
+	pub const OWNER_OF: u32 = 0x6352211e;
+	pub const NAME: u32 = 0x06fdde03;
+	pub const SYMBOL: u32 = 0x95d89b41;
+	pub const TOKEN_URI: u32 = 0xc87b56dd;
+	pub const APPROVE: u32 = 0x095ea7b3;
+	pub const GET_APPROVED: u32 = 0x081812fc;
+	pub const SET_APPROVAL_FOR_ALL: u32 = 0xa22cb465;
+	pub const IS_APPROVED_FOR_ALL: u32 = 0xe985e9c5;
+	pub const TRANSFER_FROM: u32 = 0x23b872dd;
+	pub const SAFE_TRANSFER_FROM: u32 = 0x42842e0e;
+	pub const SAFE_TRANSFER_FROM_WITH_DATA: u32 = 0xb88d4fde;
+	pub const SUPPORTS_INTERFACE: u32 = 0x01ffc9a7;
+	pub const ROYALTY_INFO: u32 = 0x2a55205a;
+}
+
+/// Interface ids answered by `supportsInterface`.
+const SUPPORTED_INTERFACES: [u32; 4] = [
+	// ERC-165
+	0x01ffc9a7,
+	// ERC-721
+	0x80ac58cd,
+	// ERC-721 metadata
+	0x5b5e139f,
+	// EIP-2981 royalties
+	0x2a55205a,
+];
+
+pub struct Erc721Precompile<R>(PhantomData<R>);
+
+impl<R> Erc721Precompile<R>
+where
+	R: pallet_bein_nft::Config + pallet_evm_account::Config + pallet_tx_pause::Config,
+	R::AccountId: IsType<AccountId32>,
+{
+	pub fn execute(
+		collection: CollectionId,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		let mut gasometer = Gasometer::new(target_gas);
+		let mut reader = EvmDataReader::new(input);
+		let address = pallet_bein_nft::Pallet::<R>::collection_address(collection);
+
+		match reader.read_selector()? {
+			action::BALANCE_OF => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let owner = Self::account(reader.read_address()?);
+				let count = pallet_bein_nft::Pallet::<R>::balance_of(collection, owner);
+				Ok(gasometer.succeed(EvmDataWriter::new().write_u256(count.into()).build(), vec![]))
+			}
+			action::OWNER_OF => {
+				gasometer.record_reads_writes::<R>(2, 0)?;
+				let item = reader.read_u32()?;
+				let owner = pallet_bein_nft::Pallet::<R>::owner_of(collection, item)
+					.ok_or_else(|| revert(b"unknown item"))?;
+				Ok(gasometer.succeed(
+					EvmDataWriter::new().write_address(Self::evm_address(&owner)).build(),
+					vec![],
+				))
+			}
+			action::NAME => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let metadata = pallet_bein_nft::Pallet::<R>::collection_metadata(collection);
+				Ok(gasometer.succeed(EvmDataWriter::new().write_bytes(&metadata.name).build(), vec![]))
+			}
+			action::SYMBOL => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let metadata = pallet_bein_nft::Pallet::<R>::collection_metadata(collection);
+				Ok(gasometer.succeed(EvmDataWriter::new().write_bytes(&metadata.symbol).build(), vec![]))
+			}
+			action::TOKEN_URI => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let item = reader.read_u32()?;
+				let uri = pallet_bein_nft::Pallet::<R>::item_metadata(collection, item);
+				Ok(gasometer.succeed(EvmDataWriter::new().write_bytes(&uri).build(), vec![]))
+			}
+			action::GET_APPROVED => {
+				gasometer.record_reads_writes::<R>(2, 0)?;
+				let item = reader.read_u32()?;
+				let details = pallet_bein_nft::Pallet::<R>::items(collection, item)
+					.ok_or_else(|| revert(b"unknown item"))?;
+				let approved = details.approved.map(|a| Self::evm_address(&a)).unwrap_or_default();
+				Ok(gasometer.succeed(EvmDataWriter::new().write_address(approved).build(), vec![]))
+			}
+			action::IS_APPROVED_FOR_ALL => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let owner = Self::account(reader.read_address()?);
+				let operator = Self::account(reader.read_address()?);
+				let approved =
+					pallet_bein_nft::Pallet::<R>::is_approved_for_all(collection, (owner, operator));
+				Ok(gasometer.succeed(EvmDataWriter::new().write_bool(approved).build(), vec![]))
+			}
+			action::APPROVE => {
+				check_not_static(is_static)?;
+				check_called_at(context, address)?;
+				check_not_paused::<R, Nft<R>>(&mut gasometer, "approve_transfer")?;
+				gasometer.record_reads_writes::<R>(2, 1)?;
+				let delegate = reader.read_address()?;
+				let item = reader.read_u32()?;
+				let caller = Self::account(context.caller);
+
+				// Approving the zero address clears the approval.
+				let delegate_account = if delegate.is_zero() {
+					None
+				} else {
+					Some(Self::account(delegate))
+				};
+				pallet_bein_nft::Pallet::<R>::do_approve(collection, item, &caller, delegate_account)
+					.map_err(dispatch_revert)?;
+				let owner = pallet_bein_nft::Pallet::<R>::owner_of(collection, item)
+					.map(|owner| Self::evm_address(&owner))
+					.unwrap_or_default();
+
+				Ok(gasometer.succeed(
+					vec![],
+					vec![log4(
+						address,
+						H256::from(SELECTOR_LOG_APPROVAL),
+						address_to_h256(owner),
+						address_to_h256(delegate),
+						u256_to_h256(item.into()),
+						vec![],
+					)],
+				))
+			}
+			action::SET_APPROVAL_FOR_ALL => {
+				check_not_static(is_static)?;
+				check_called_at(context, address)?;
+				check_not_paused::<R, Nft<R>>(&mut gasometer, "set_approval_for_all")?;
+				gasometer.record_reads_writes::<R>(1, 1)?;
+				let operator = reader.read_address()?;
+				let approved = reader.read_bool()?;
+
+				pallet_bein_nft::Pallet::<R>::do_set_approval_for_all(
+					collection,
+					&Self::account(context.caller),
+					&Self::account(operator),
+					approved,
+				)
+				.map_err(dispatch_revert)?;
+
+				Ok(gasometer.succeed(
+					vec![],
+					vec![log3(
+						address,
+						H256::from(SELECTOR_LOG_APPROVAL_FOR_ALL),
+						address_to_h256(context.caller),
+						address_to_h256(operator),
+						EvmDataWriter::new().write_bool(approved).build(),
+					)],
+				))
+			}
+			selector @ action::TRANSFER_FROM |
+			selector @ action::SAFE_TRANSFER_FROM |
+			selector @ action::SAFE_TRANSFER_FROM_WITH_DATA => {
+				check_not_static(is_static)?;
+				check_called_at(context, address)?;
+				check_not_paused::<R, Nft<R>>(&mut gasometer, "transfer")?;
+				gasometer.record_reads_writes::<R>(5, 4)?;
+				let from = reader.read_address()?;
+				let to = reader.read_address()?;
+				let item = reader.read_u32()?;
+
+				// A precompile cannot call `onERC721Received`, so safe transfers are only
+				// accepted towards accounts without code.
+				if selector != action::TRANSFER_FROM &&
+					!pallet_evm::AccountCodes::<R>::get(to).is_empty()
+				{
+					return Err(revert(b"safe transfer to contracts is not supported"));
+				}
+
+				let owner = pallet_bein_nft::Pallet::<R>::owner_of(collection, item)
+					.ok_or_else(|| revert(b"unknown item"))?;
+				if owner != Self::account(from) {
+					return Err(revert(b"from is not the owner"));
+				}
+				pallet_bein_nft::Pallet::<R>::do_transfer(
+					collection,
+					item,
+					&Self::account(context.caller),
+					&Self::account(to),
+				)
+				.map_err(dispatch_revert)?;
+
+				Ok(gasometer.succeed(
+					vec![],
+					vec![log4(
+						address,
+						H256::from(SELECTOR_LOG_TRANSFER),
+						address_to_h256(from),
+						address_to_h256(to),
+						u256_to_h256(item.into()),
+						vec![],
+					)],
+				))
+			}
+			action::SUPPORTS_INTERFACE => {
+				gasometer.record_cost(100)?;
+				let word = reader.read_u256()?;
+				// `bytes4` arguments are left aligned in their word.
+				let interface = (word >> 224).low_u32();
+				Ok(gasometer.succeed(
+					EvmDataWriter::new().write_bool(SUPPORTED_INTERFACES.contains(&interface)).build(),
+					vec![],
+				))
+			}
+			action::ROYALTY_INFO => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let _item = reader.read_u32()?;
+				let sale_price = reader.read_u256()?;
+				let (creator, royalty) = pallet_bein_nft::Pallet::<R>::royalty(collection)
+					.ok_or_else(|| revert(b"unknown collection"))?;
+				let amount = sale_price
+					.checked_mul(U256::from(royalty.deconstruct()))
+					.ok_or_else(|| error("sale price overflow"))? /
+					U256::from(1_000_000_000u32);
+
+				Ok(gasometer.succeed(
+					EvmDataWriter::new()
+						.write_address(Self::evm_address(&creator))
+						.write_u256(amount)
+						.build(),
+					vec![],
+				))
+			}
+			_ => Err(error("unknown selector")),
+		}
+	}
+
+	fn account(address: H160) -> R::AccountId {
+		<R as pallet_evm::Config>::AddressMapping::into_account_id(address)
+	}
+
+	/// The EVM address of an account, or the first 20 bytes of an account that has none, so
+	/// that Substrate-only owners are not reported as the zero address.
+	fn evm_address(account: &R::AccountId) -> H160 {
+		pallet_evm_account::Pallet::<R>::evm_address_of(account)
+			.unwrap_or_else(|| H160::from_slice(&account.into_ref().as_ref()[0..20]))
+	}
+}
@@ -0,0 +1,79 @@
+//! Read-only view of `pallet_groups`, so contracts installed into a group can check who
+//! belongs to it.
+
+use pallet_evm::{AddressMapping, PrecompileResult};
+use pallet_groups::{Extension, GroupMembership};
+use sp_core::{crypto::AccountId32, H160};
+use sp_runtime::traits::IsType;
+use sp_std::{marker::PhantomData, vec};
+
+use super::utils::{error, revert, EvmDataReader, EvmDataWriter, Gasometer};
+
+/// Function selectors of the groups interface.
+mod action {
+	pub const IS_MEMBER: u32 = 0xfbb601e4;
+	pub const IS_ADMIN: u32 = 0xcf925bdf;
+	pub const OWNER_OF: u32 = 0x23b7ec3b;
+	pub const HAS_EXTENSION: u32 = 0x475127f1;
+	pub const MEMBER_COUNT: u32 = 0xd9c53cd9;
+}
+
+pub struct GroupsPrecompile<R>(PhantomData<R>);
+
+impl<R> GroupsPrecompile<R>
+where
+	R: pallet_evm::Config + pallet_groups::Config + pallet_evm_account::Config,
+	R::AccountId: IsType<AccountId32>,
+{
+	pub fn execute(input: &[u8], target_gas: Option<u64>) -> PrecompileResult {
+		let mut gasometer = Gasometer::new(target_gas);
+		let mut reader = EvmDataReader::new(input);
+
+		match reader.read_selector()? {
+			action::IS_MEMBER => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let group = reader.read_u32()?;
+				let who = Self::account(reader.read_address()?);
+				let member = pallet_groups::Pallet::<R>::is_member(group, &who);
+				Ok(gasometer.succeed(EvmDataWriter::new().write_bool(member).build(), vec![]))
+			}
+			action::IS_ADMIN => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let group = reader.read_u32()?;
+				let who = Self::account(reader.read_address()?);
+				let admin = pallet_groups::Pallet::<R>::is_admin(group, &who);
+				Ok(gasometer.succeed(EvmDataWriter::new().write_bool(admin).build(), vec![]))
+			}
+			action::OWNER_OF => {
+				gasometer.record_reads_writes::<R>(2, 0)?;
+				let group = reader.read_u32()?;
+				let details =
+					pallet_groups::Pallet::<R>::groups(group).ok_or_else(|| revert(b"unknown group"))?;
+				let owner =
+					pallet_evm_account::Pallet::<R>::evm_address_of(&details.owner).unwrap_or_default();
+				Ok(gasometer.succeed(EvmDataWriter::new().write_address(owner).build(), vec![]))
+			}
+			action::HAS_EXTENSION => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let group = reader.read_u32()?;
+				let contract = reader.read_address()?;
+				let installed =
+					pallet_groups::Pallet::<R>::has_extension(group, &Extension::Contract(contract));
+				Ok(gasometer.succeed(EvmDataWriter::new().write_bool(installed).build(), vec![]))
+			}
+			action::MEMBER_COUNT => {
+				gasometer.record_reads_writes::<R>(1, 0)?;
+				let group = reader.read_u32()?;
+				let count = pallet_groups::Pallet::<R>::groups(group)
+					.map(|details| details.members)
+					.unwrap_or_default();
+				Ok(gasometer.succeed(EvmDataWriter::new().write_u256(count.into()).build(), vec![]))
+			}
+			_ => Err(error("unknown selector")),
+		}
+	}
+
+	fn account(address: H160) -> R::AccountId {
+		<R as pallet_evm::Config>::AddressMapping::into_account_id(address)
+	}
+}
@@ -0,0 +1,65 @@
+//! Read-only view of `pallet_identity` by EVM address, so dapps can show the name of an
+//! account instead of its address.
+//!
+//! Addresses are mapped with the runtime `AddressMapping`, so an address claimed through
+//! `pallet_evm_account` resolves to the identity of the Substrate account that claimed it.
+
+use pallet_evm::{AddressMapping, PrecompileResult};
+use pallet_identity::{Data, Judgement};
+use sp_core::H160;
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+
+use super::utils::{error, EvmDataReader, EvmDataWriter, Gasometer};
+
+/// Function selectors of the identity interface.
+mod action {
+	pub const HAS_IDENTITY: u32 = 0x237f1a21;
+	pub const DISPLAY_NAME: u32 = 0x5ae13b00;
+	pub const IS_VERIFIED: u32 = 0xb9209e33;
+}
+
+pub struct IdentityPrecompile<R>(PhantomData<R>);
+
+impl<R> IdentityPrecompile<R>
+where
+	R: pallet_evm::Config + pallet_identity::Config,
+{
+	pub fn execute(input: &[u8], target_gas: Option<u64>) -> PrecompileResult {
+		let mut gasometer = Gasometer::new(target_gas);
+		let mut reader = EvmDataReader::new(input);
+
+		let selector = reader.read_selector()?;
+		// Every call reads the address mapping and the identity of the account.
+		gasometer.record_reads_writes::<R>(2, 0)?;
+		let who = Self::account(reader.read_address()?);
+		let registration = pallet_identity::Pallet::<R>::identity(who);
+
+		match selector {
+			action::HAS_IDENTITY => Ok(gasometer.succeed(
+				EvmDataWriter::new().write_bool(registration.is_some()).build(),
+				vec![],
+			)),
+			action::DISPLAY_NAME => {
+				let name = match registration.map(|registration| registration.info.display) {
+					Some(Data::Raw(name)) => name.to_vec(),
+					_ => Vec::new(),
+				};
+				Ok(gasometer.succeed(EvmDataWriter::new().write_bytes(&name).build(), vec![]))
+			}
+			action::IS_VERIFIED => {
+				// A registrar vouched for the identity.
+				let verified = registration.map_or(false, |registration| {
+					registration.judgements.iter().any(|(_, judgement)| {
+						matches!(judgement, Judgement::Reasonable | Judgement::KnownGood)
+					})
+				});
+				Ok(gasometer.succeed(EvmDataWriter::new().write_bool(verified).build(), vec![]))
+			}
+			_ => Err(error("unknown selector")),
+		}
+	}
+
+	fn account(address: H160) -> R::AccountId {
+		<R as pallet_evm::Config>::AddressMapping::into_account_id(address)
+	}
+}
@@ -0,0 +1,110 @@
+use core::convert::TryFrom;
+use pallet_evm::{Context, Precompile, PrecompileResult, PrecompileSet};
+use pallet_social_token::BalanceOf as TokenBalanceOf;
+use sp_core::{crypto::AccountId32, H160, U256};
+use sp_runtime::traits::IsType;
+use sp_std::marker::PhantomData;
+
+use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_sha3fips::Sha3FIPS256;
+use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+
+mod erc20;
+mod erc721;
+mod groups;
+mod identity;
+mod utils;
+
+use erc20::Erc20Precompile;
+use erc721::Erc721Precompile;
+use groups::GroupsPrecompile;
+use identity::IdentityPrecompile;
+
+pub struct FrontierPrecompiles<R>(PhantomData<R>);
+
+impl<R> FrontierPrecompiles<R>
+where
+	R: pallet_evm::Config,
+{
+	pub fn new() -> Self {
+		Self(Default::default())
+	}
+	pub fn used_addresses() -> sp_std::vec::Vec<H160> {
+		sp_std::vec![1, 2, 3, 4, 5, 1024, 1025, 2048, 2049]
+			.into_iter()
+			.map(|x| hash(x))
+			.collect()
+	}
+}
+impl<R> PrecompileSet for FrontierPrecompiles<R>
+where
+	R: pallet_social_token::Config
+		+ pallet_bein_nft::Config
+		+ pallet_groups::Config
+		+ pallet_identity::Config
+		+ pallet_evm_account::Config
+		+ pallet_tx_pause::Config,
+	R::AccountId: IsType<AccountId32>,
+	TokenBalanceOf<R>: TryFrom<U256> + Into<U256>,
+{
+	fn execute(
+		&self,
+		address: H160,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> Option<PrecompileResult> {
+		match address {
+			// Ethereum precompiles :
+			a if a == hash(1) => Some(ECRecover::execute(input, target_gas, context, is_static)),
+			a if a == hash(2) => Some(Sha256::execute(input, target_gas, context, is_static)),
+			a if a == hash(3) => Some(Ripemd160::execute(input, target_gas, context, is_static)),
+			a if a == hash(4) => Some(Identity::execute(input, target_gas, context, is_static)),
+			a if a == hash(5) => Some(Modexp::execute(input, target_gas, context, is_static)),
+			// Non-Frontier specific nor Ethereum precompiles :
+			a if a == hash(1024) => {
+				Some(Sha3FIPS256::execute(input, target_gas, context, is_static))
+			}
+			a if a == hash(1025) => Some(ECRecoverPublicKey::execute(
+				input, target_gas, context, is_static,
+			)),
+			// Bein precompiles :
+			a if a == hash(2048) => Some(GroupsPrecompile::<R>::execute(input, target_gas)),
+			a if a == hash(2049) => Some(IdentityPrecompile::<R>::execute(input, target_gas)),
+			a => {
+				if let Some(id) = Self::social_token(a) {
+					return Some(Erc20Precompile::<R>::execute(id, input, target_gas, context, is_static));
+				}
+				if let Some(id) = Self::nft_collection(a) {
+					return Some(Erc721Precompile::<R>::execute(id, input, target_gas, context, is_static));
+				}
+				None
+			}
+		}
+	}
+
+	fn is_precompile(&self, address: H160) -> bool {
+		Self::used_addresses().contains(&address) ||
+			Self::social_token(address).is_some() ||
+			Self::nft_collection(address).is_some()
+	}
+}
+
+impl<R: pallet_social_token::Config + pallet_bein_nft::Config> FrontierPrecompiles<R> {
+	/// Id of the existing social token served at `address`, if any.
+	fn social_token(address: H160) -> Option<pallet_social_token::TokenId> {
+		pallet_social_token::Pallet::<R>::token_id(address)
+			.filter(|id| pallet_social_token::Tokens::<R>::contains_key(id))
+	}
+
+	/// Id of the existing NFT collection served at `address`, if any.
+	fn nft_collection(address: H160) -> Option<pallet_bein_nft::CollectionId> {
+		pallet_bein_nft::Pallet::<R>::collection_id(address)
+			.filter(|id| pallet_bein_nft::Collections::<R>::contains_key(id))
+	}
+}
+
+fn hash(a: u64) -> H160 {
+	H160::from_low_u64_be(a)
+}
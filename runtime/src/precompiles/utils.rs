@@ -0,0 +1,228 @@
+//! Minimal Solidity ABI encoding and gas helpers shared by the Bein precompiles.
+
+use frame_support::{
+	traits::{CallMetadata, Get, PalletInfoAccess},
+	weights::Weight,
+};
+use pallet_evm::{
+	Context, ExitError, ExitRevert, ExitSucceed, GasWeightMapping, Log, PrecompileFailure,
+	PrecompileOutput,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+/// Reads ABI encoded call data, one 32 byte word at a time after the selector.
+pub struct EvmDataReader<'a> {
+	input: &'a [u8],
+	cursor: usize,
+}
+
+impl<'a> EvmDataReader<'a> {
+	pub fn new(input: &'a [u8]) -> Self {
+		Self { input, cursor: 0 }
+	}
+
+	pub fn read_selector(&mut self) -> Result<u32, PrecompileFailure> {
+		let bytes = self.input.get(0..4).ok_or_else(|| error("tried to parse selector out of bounds"))?;
+		self.cursor = 4;
+		let mut selector = [0u8; 4];
+		selector.copy_from_slice(bytes);
+		Ok(u32::from_be_bytes(selector))
+	}
+
+	fn read_word(&mut self) -> Result<&'a [u8], PrecompileFailure> {
+		let word = self
+			.input
+			.get(self.cursor..self.cursor + 32)
+			.ok_or_else(|| error("tried to parse argument out of bounds"))?;
+		self.cursor += 32;
+		Ok(word)
+	}
+
+	pub fn read_u256(&mut self) -> Result<U256, PrecompileFailure> {
+		Ok(U256::from_big_endian(self.read_word()?))
+	}
+
+	pub fn read_address(&mut self) -> Result<H160, PrecompileFailure> {
+		Ok(H160::from_slice(&self.read_word()?[12..32]))
+	}
+
+	pub fn read_bool(&mut self) -> Result<bool, PrecompileFailure> {
+		Ok(!self.read_u256()?.is_zero())
+	}
+
+	pub fn read_u32(&mut self) -> Result<u32, PrecompileFailure> {
+		let value = self.read_u256()?;
+		if value > U256::from(u32::MAX) {
+			return Err(error("value does not fit in uint32"));
+		}
+		Ok(value.low_u32())
+	}
+}
+
+/// Builds ABI encoded return data.
+#[derive(Default)]
+pub struct EvmDataWriter {
+	data: Vec<u8>,
+}
+
+impl EvmDataWriter {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn write_u256(mut self, value: U256) -> Self {
+		let mut word = [0u8; 32];
+		value.to_big_endian(&mut word);
+		self.data.extend_from_slice(&word);
+		self
+	}
+
+	pub fn write_bool(self, value: bool) -> Self {
+		self.write_u256(if value { U256::one() } else { U256::zero() })
+	}
+
+	pub fn write_address(mut self, value: H160) -> Self {
+		self.data.extend_from_slice(&[0u8; 12]);
+		self.data.extend_from_slice(value.as_bytes());
+		self
+	}
+
+	/// Writes a single dynamic `bytes` or `string` return value.
+	pub fn write_bytes(self, value: &[u8]) -> Self {
+		let mut writer = self.write_u256(U256::from(32)).write_u256(U256::from(value.len()));
+		writer.data.extend_from_slice(value);
+		let padding = (32 - value.len() % 32) % 32;
+		writer.data.extend(sp_std::iter::repeat(0u8).take(padding));
+		writer
+	}
+
+	pub fn build(self) -> Vec<u8> {
+		self.data
+	}
+}
+
+/// Tracks the gas used by a precompile against the gas it was given.
+pub struct Gasometer {
+	used: u64,
+	target_gas: Option<u64>,
+}
+
+impl Gasometer {
+	pub fn new(target_gas: Option<u64>) -> Self {
+		Self { used: 0, target_gas }
+	}
+
+	pub fn record_cost(&mut self, cost: u64) -> Result<(), PrecompileFailure> {
+		self.used = self.used.checked_add(cost).ok_or(PrecompileFailure::Error {
+			exit_status: ExitError::OutOfGas,
+		})?;
+		match self.target_gas {
+			Some(target) if self.used > target => {
+				Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas })
+			}
+			_ => Ok(()),
+		}
+	}
+
+	pub fn record_weight<R: pallet_evm::Config>(
+		&mut self,
+		weight: Weight,
+	) -> Result<(), PrecompileFailure> {
+		self.record_cost(R::GasWeightMapping::weight_to_gas(weight))
+	}
+
+	pub fn record_reads_writes<R: pallet_evm::Config>(
+		&mut self,
+		reads: Weight,
+		writes: Weight,
+	) -> Result<(), PrecompileFailure> {
+		self.record_weight::<R>(<R as frame_system::Config>::DbWeight::get().reads_writes(reads, writes))
+	}
+
+	pub fn succeed(&self, output: Vec<u8>, logs: Vec<Log>) -> PrecompileOutput {
+		PrecompileOutput { exit_status: ExitSucceed::Returned, cost: self.used, output, logs }
+	}
+}
+
+pub fn error(message: &'static str) -> PrecompileFailure {
+	PrecompileFailure::Error { exit_status: ExitError::Other(message.into()) }
+}
+
+pub fn revert(message: &[u8]) -> PrecompileFailure {
+	PrecompileFailure::Revert {
+		exit_status: ExitRevert::Reverted,
+		output: message.to_vec(),
+		cost: 0,
+	}
+}
+
+/// Reverts with the name of the pallet error that made a dispatch fail.
+pub fn dispatch_revert(err: DispatchError) -> PrecompileFailure {
+	let message: &'static str = err.into();
+	revert(message.as_bytes())
+}
+
+pub fn check_not_static(is_static: bool) -> Result<(), PrecompileFailure> {
+	if is_static {
+		return Err(error("can't call non-static function in static context"));
+	}
+	Ok(())
+}
+
+/// Reject calls that do not run at the precompile's own `address`, i.e. DELEGATECALL and
+/// CALLCODE: the caller would then be the user of the calling contract, whose assets that
+/// contract could move.
+pub fn check_called_at(context: &Context, address: H160) -> Result<(), PrecompileFailure> {
+	if context.address != address {
+		return Err(revert(b"cannot be called with DELEGATECALL or CALLCODE"));
+	}
+	Ok(())
+}
+
+/// Reject calls whose Substrate counterpart, call `function_name` of pallet `P`, is paused by
+/// `TxPause`. Precompiles do not dispatch calls, so the runtime's `BaseCallFilter` never sees
+/// them.
+pub fn check_not_paused<R, P>(
+	gasometer: &mut Gasometer,
+	function_name: &'static str,
+) -> Result<(), PrecompileFailure>
+where
+	R: pallet_evm::Config + pallet_tx_pause::Config,
+	P: PalletInfoAccess,
+{
+	gasometer.record_reads_writes::<R>(2, 0)?;
+	let metadata = CallMetadata { function_name, pallet_name: P::name() };
+	if pallet_tx_pause::Pallet::<R>::is_paused(&metadata) {
+		return Err(revert(b"call is paused"));
+	}
+	Ok(())
+}
+
+pub fn address_to_h256(address: H160) -> H256 {
+	H256::from(address)
+}
+
+pub fn u256_to_h256(value: U256) -> H256 {
+	let mut word = [0u8; 32];
+	value.to_big_endian(&mut word);
+	H256::from(word)
+}
+
+/// An event log with a signature topic and two indexed arguments.
+pub fn log3(address: H160, signature: H256, topic1: H256, topic2: H256, data: Vec<u8>) -> Log {
+	Log { address, topics: sp_std::vec![signature, topic1, topic2], data }
+}
+
+/// An event log with a signature topic and three indexed arguments.
+pub fn log4(
+	address: H160,
+	signature: H256,
+	topic1: H256,
+	topic2: H256,
+	topic3: H256,
+	data: Vec<u8>,
+) -> Log {
+	Log { address, topics: sp_std::vec![signature, topic1, topic2, topic3], data }
+}
@@ -0,0 +1,351 @@
+use super::*;
+use frame_support::{
+	assert_ok,
+	traits::{Currency, EstimateCallFee, Get},
+	weights::{DispatchClass, GetDispatchInfo},
+};
+use pallet_feeless::NestedFeelessCalls;
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{
+	traits::SignedExtension,
+	transaction_validity::{InvalidTransaction, TransactionValidity},
+};
+use pallet_evm::{AddressMapping, Context, GasWeightMapping, PrecompileFailure, PrecompileSet};
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Substrate account of an EVM address, funded so that it can pay deposits.
+fn funded_evm_account(address: H160) -> AccountId {
+	let account = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address);
+	Balances::make_free_balance_be(&account, 1_000 * BIC);
+	account
+}
+
+/// ABI call data of `selector` with 32 byte arguments.
+fn evm_input(selector: u32, args: &[H256]) -> Vec<u8> {
+	let mut input = selector.to_be_bytes().to_vec();
+	for arg in args {
+		input.extend_from_slice(arg.as_bytes());
+	}
+	input
+}
+
+/// Execute a precompile the way a contract at `context_address` reaches it: at `address` with
+/// CALL, or with its own address as context with DELEGATECALL.
+fn call_precompile(
+	address: H160,
+	context_address: H160,
+	caller: H160,
+	input: &[u8],
+) -> Option<Result<pallet_evm::PrecompileOutput, PrecompileFailure>> {
+	let context = Context { address: context_address, caller, apparent_value: U256::zero() };
+	PrecompilesValue::get().execute(address, input, None, &context, false)
+}
+
+fn is_revert(result: Option<Result<pallet_evm::PrecompileOutput, PrecompileFailure>>) -> bool {
+	matches!(result, Some(Err(PrecompileFailure::Revert { .. })))
+}
+
+#[test]
+fn erc20_rejects_delegatecall() {
+	new_test_ext().execute_with(|| {
+		let alice = H160::repeat_byte(0xaa);
+		let malicious = H160::repeat_byte(0xee);
+		let owner = funded_evm_account(alice);
+		assert_ok!(SocialToken::create(
+			Origin::signed(owner.clone()),
+			b"Token".to_vec(),
+			b"TOK".to_vec(),
+			18,
+			1_000,
+			1_000,
+			pallet_social_token::IssuancePolicy::Fixed,
+		));
+		let token = SocialToken::token_address(0);
+		let transfer = evm_input(0xa9059cbb, &[H256::from(malicious), H256::from_low_u64_be(400)]);
+		let approve = evm_input(0x095ea7b3, &[H256::from(malicious), H256::from_low_u64_be(400)]);
+
+		assert!(is_revert(call_precompile(token, malicious, alice, &transfer)));
+		assert!(is_revert(call_precompile(token, malicious, alice, &approve)));
+		assert_eq!(SocialToken::balance_of(0, &owner), 1_000);
+
+		assert!(matches!(call_precompile(token, token, alice, &transfer), Some(Ok(_))));
+		assert_eq!(SocialToken::balance_of(0, &owner), 600);
+	});
+}
+
+fn block_gas_limit() -> u64 {
+	<Runtime as pallet_evm::Config>::BlockGasLimit::get().low_u64()
+}
+
+#[test]
+fn erc721_rejects_delegatecall_and_reports_substrate_owners() {
+	new_test_ext().execute_with(|| {
+		let alice = H160::repeat_byte(0xaa);
+		let malicious = H160::repeat_byte(0xee);
+		let owner = funded_evm_account(alice);
+		let substrate_only = AccountId::from([7u8; 32]);
+		assert_ok!(Nft::create_collection(
+			Origin::signed(owner.clone()),
+			b"Collection".to_vec(),
+			b"COL".to_vec(),
+			Perbill::zero(),
+		));
+		assert_ok!(Nft::mint(Origin::signed(owner.clone()), 0, 1, owner.clone().into(), vec![]));
+		assert_ok!(Nft::mint(Origin::signed(owner.clone()), 0, 2, substrate_only.into(), vec![]));
+		let collection = Nft::collection_address(0);
+		let transfer_from = evm_input(
+			0x23b872dd,
+			&[H256::from(alice), H256::from(malicious), H256::from_low_u64_be(1)],
+		);
+		let approve_all =
+			evm_input(0xa22cb465, &[H256::from(malicious), H256::from_low_u64_be(1)]);
+
+		assert!(is_revert(call_precompile(collection, malicious, alice, &transfer_from)));
+		assert!(is_revert(call_precompile(collection, malicious, alice, &approve_all)));
+		assert_eq!(Nft::items(0, 1).map(|item| item.owner), Some(owner));
+
+		let owner_of = evm_input(0x6352211e, &[H256::from_low_u64_be(2)]);
+		let output = call_precompile(collection, collection, alice, &owner_of).unwrap().unwrap();
+		assert_eq!(output.output, H256::from(H160::repeat_byte(7)).as_bytes().to_vec());
+	});
+}
+
+#[test]
+fn paused_calls_cannot_be_made_through_precompiles() {
+	new_test_ext().execute_with(|| {
+		let alice = H160::repeat_byte(0xaa);
+		let bob = H160::repeat_byte(0xbb);
+		let owner = funded_evm_account(alice);
+		assert_ok!(SocialToken::create(
+			Origin::signed(owner.clone()),
+			b"Token".to_vec(),
+			b"TOK".to_vec(),
+			18,
+			1_000,
+			1_000,
+			pallet_social_token::IssuancePolicy::Fixed,
+		));
+		assert_ok!(Nft::create_collection(
+			Origin::signed(owner.clone()),
+			b"Collection".to_vec(),
+			b"COL".to_vec(),
+			Perbill::zero(),
+		));
+		assert_ok!(Nft::mint(Origin::signed(owner.clone()), 0, 1, owner.clone().into(), vec![]));
+		let token = SocialToken::token_address(0);
+		let collection = Nft::collection_address(0);
+		let transfer = evm_input(0xa9059cbb, &[H256::from(bob), H256::from_low_u64_be(400)]);
+		let transfer_from = evm_input(
+			0x23b872dd,
+			&[H256::from(alice), H256::from(bob), H256::from_low_u64_be(1)],
+		);
+
+		assert_ok!(TxPause::pause(Origin::root(), b"SocialToken".to_vec(), None));
+		assert_ok!(TxPause::pause(
+			Origin::root(),
+			b"Nft".to_vec(),
+			Some(b"transfer".to_vec())
+		));
+		assert!(is_revert(call_precompile(token, token, alice, &transfer)));
+		assert!(is_revert(call_precompile(collection, collection, alice, &transfer_from)));
+		assert_eq!(SocialToken::balance_of(0, &owner), 1_000);
+		assert_eq!(Nft::items(0, 1).map(|item| item.owner), Some(owner.clone()));
+
+		assert_ok!(TxPause::unpause(Origin::root(), b"SocialToken".to_vec(), None));
+		assert_ok!(TxPause::unpause(
+			Origin::root(),
+			b"Nft".to_vec(),
+			Some(b"transfer".to_vec())
+		));
+		assert!(matches!(call_precompile(token, token, alice, &transfer), Some(Ok(_))));
+		let result = call_precompile(collection, collection, alice, &transfer_from);
+		assert!(matches!(result, Some(Ok(_))));
+		assert_eq!(SocialToken::balance_of(0, &owner), 600);
+		assert_ne!(Nft::items(0, 1).map(|item| item.owner), Some(owner));
+	});
+}
+
+#[test]
+fn block_production_and_governance_cannot_be_paused() {
+	new_test_ext().execute_with(|| {
+		for pallet in [
+			"System",
+			"Timestamp",
+			"Aura",
+			"Grandpa",
+			"Session",
+			"Council",
+			"CouncilMembership",
+			"TechnicalCommittee",
+			"TechnicalMembership",
+			"Democracy",
+			"Scheduler",
+			"TxPause",
+		] {
+			assert_eq!(
+				TxPause::pause(Origin::root(), pallet.as_bytes().to_vec(), None),
+				Err(pallet_tx_pause::Error::<Runtime>::Unpausable.into())
+			);
+		}
+		assert_ok!(TxPause::pause(Origin::root(), b"Nft".to_vec(), None));
+	});
+}
+
+fn account(byte: u8) -> AccountId {
+	let account = AccountId::from([byte; 32]);
+	Balances::make_free_balance_be(&account, 1_000 * BIC);
+	account
+}
+
+fn transfer_call(dest: &AccountId) -> Call {
+	Call::Balances(pallet_balances::Call::transfer { dest: dest.clone().into(), value: BIC })
+}
+
+fn feeless(call: Call) -> Call {
+	Call::Feeless(pallet_feeless::Call::feeless_call { call: Box::new(call) })
+}
+
+fn batch_all(calls: Vec<Call>) -> Call {
+	Call::Utility(pallet_utility::Call::batch_all { calls })
+}
+
+fn proxy(real: &AccountId, call: Call) -> Call {
+	Call::Proxy(pallet_proxy::Call::proxy {
+		real: real.clone(),
+		force_proxy_type: None,
+		call: Box::new(call),
+	})
+}
+
+fn validate_feeless(who: &AccountId, call: &Call) -> TransactionValidity {
+	pallet_feeless::CheckFeeless::<Runtime>::new().validate(who, call, &call.get_dispatch_info(), 0)
+}
+
+#[test]
+fn feeless_calls_are_found_in_batches_and_proxy_calls() {
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (account(1), account(2));
+		let transfer = transfer_call(&bob);
+
+		assert_eq!(
+			FeelessWrappers::nested_feeless_calls(
+				&alice,
+				&batch_all(vec![feeless(transfer.clone()), feeless(transfer.clone())])
+			),
+			Some(vec![(alice.clone(), transfer.clone()), (alice.clone(), transfer.clone())])
+		);
+		// Only wrappers of nothing but feeless calls count.
+		assert_eq!(FeelessWrappers::nested_feeless_calls(&alice, &feeless(transfer.clone())), None);
+		assert_eq!(
+			FeelessWrappers::nested_feeless_calls(
+				&alice,
+				&batch_all(vec![feeless(transfer.clone()), transfer.clone()])
+			),
+			None
+		);
+
+		// Made for the real account, once the signer is a proxy allowed to.
+		let proxied = proxy(&alice, feeless(transfer.clone()));
+		assert_eq!(FeelessWrappers::nested_feeless_calls(&bob, &proxied), None);
+		let add_proxy = |proxy_type| {
+			Proxy::add_proxy(Origin::signed(alice.clone()), bob.clone(), proxy_type, 0)
+		};
+		assert_ok!(add_proxy(ProxyType::NonTransfer));
+		assert_eq!(FeelessWrappers::nested_feeless_calls(&bob, &proxied), None);
+		assert_ok!(add_proxy(ProxyType::Any));
+		assert_eq!(
+			FeelessWrappers::nested_feeless_calls(&bob, &proxied),
+			Some(vec![(alice, transfer)])
+		);
+	});
+}
+
+#[test]
+fn wrappers_of_feeless_calls_pay_no_transaction_fee() {
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (account(1), account(2));
+		let wrapper = batch_all(vec![feeless(transfer_call(&bob))]);
+		let plain = batch_all(vec![transfer_call(&bob)]);
+
+		let withdraw_fee = |call: &Call| {
+			<Runtime as pallet_transaction_payment::Config>::OnChargeTransaction::withdraw_fee(
+				&alice,
+				call,
+				&call.get_dispatch_info(),
+				BIC,
+				0,
+			)
+		};
+
+		assert_ok!(withdraw_fee(&wrapper));
+		assert_eq!(Balances::free_balance(&alice), 1_000 * BIC);
+		assert_ok!(withdraw_fee(&plain));
+		assert_eq!(Balances::free_balance(&alice), 999 * BIC);
+	});
+}
+
+#[test]
+fn nested_feeless_calls_are_validated_together() {
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (account(1), account(2));
+		let transfer = transfer_call(&bob);
+		let fee = TransactionPayment::estimate_call_fee(&transfer, ().into());
+		let one = batch_all(vec![feeless(transfer.clone())]);
+		let two = batch_all(vec![feeless(transfer.clone()), feeless(transfer)]);
+
+		// Without bandwidth, the balance of alice covers the fee of one call but not of two.
+		Balances::make_free_balance_be(&alice, ExistentialDeposit::get() + fee + fee / 2);
+		assert!(validate_feeless(&alice, &one).unwrap().priority > 0);
+		assert_eq!(validate_feeless(&alice, &two), Err(InvalidTransaction::Payment.into()));
+	});
+}
+
+/// A signed legacy Ethereum transaction calling the zero address with `gas_limit`.
+fn ethereum_transaction(gas_limit: u64) -> ethereum::TransactionV2 {
+	ethereum::TransactionV2::Legacy(ethereum::LegacyTransaction {
+		nonce: U256::zero(),
+		gas_price: U256::one(),
+		gas_limit: U256::from(gas_limit),
+		action: ethereum::TransactionAction::Call(H160::zero()),
+		value: U256::zero(),
+		input: vec![],
+		signature: ethereum::TransactionSignature::new(
+			38,
+			H256::repeat_byte(1),
+			H256::repeat_byte(1),
+		)
+		.unwrap(),
+	})
+}
+
+#[test]
+fn transaction_at_block_gas_limit_fits_in_the_largest_normal_extrinsic() {
+	let transaction = ethereum_transaction(block_gas_limit());
+	let info = Call::Ethereum(pallet_ethereum::Call::transact { transaction }).get_dispatch_info();
+	let max_extrinsic = BlockWeights::get().get(DispatchClass::Normal).max_extrinsic.unwrap();
+	let weight = info.weight + ExtrinsicBaseWeight::get();
+
+	assert_eq!(info.class, DispatchClass::Normal);
+	assert!(weight <= max_extrinsic);
+	// Not one more unit of gas would.
+	assert!(weight + BeinGasWeightMapping::gas_to_weight(1) > max_extrinsic);
+}
+
+#[test]
+fn gas_weight_mapping_round_trips() {
+	for gas in [0, 21_000, 1_000_000, block_gas_limit()] {
+		assert_eq!(BeinGasWeightMapping::weight_to_gas(BeinGasWeightMapping::gas_to_weight(gas)), gas);
+	}
+	assert_eq!(BeinGasWeightMapping::gas_to_weight(GAS_PER_SECOND), WEIGHT_PER_SECOND);
+}
+
+#[test]
+fn gas_to_weight_saturates() {
+	assert_eq!(BeinGasWeightMapping::gas_to_weight(u64::MAX), Weight::MAX);
+}
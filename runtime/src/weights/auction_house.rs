@@ -0,0 +1,35 @@
+//! Weights for `pallet_auction_house`.
+//!
+//! Not benchmarked: the base weights are estimates and the storage accesses are counted
+//! from the code. Replace this file with benchmark output once the pallet has benchmarks.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+use sp_std::marker::PhantomData;
+
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_auction_house::WeightInfo for WeightInfo<T> {
+	fn create_auction() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn cancel_auction() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn bid() -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn settle() -> Weight {
+		(90_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+}
@@ -0,0 +1,18 @@
+//! Weights for `pallet_base_fee_target`.
+//!
+//! Not benchmarked: the base weights are estimates and the storage accesses are counted
+//! from the code. Replace this file with benchmark output once the pallet has benchmarks.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+use sp_std::marker::PhantomData;
+
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_base_fee_target::WeightInfo for WeightInfo<T> {
+	fn set_target_fullness() -> Weight {
+		(15_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,35 @@
+//! Weights for `pallet_feeless`.
+//!
+//! Not benchmarked: the base weights are estimates and the storage accesses are counted
+//! from the code. Replace this file with benchmark output once the benchmarks have run.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+use sp_std::marker::PhantomData;
+
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_feeless::WeightInfo for WeightInfo<T> {
+	fn stake_bic() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(16 as Weight))
+			.saturating_add(DbWeight::get().writes(13 as Weight))
+	}
+	fn unstake_bic() -> Weight {
+		(65_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(17 as Weight))
+			.saturating_add(DbWeight::get().writes(17 as Weight))
+	}
+	fn claim_rewards() -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(20 as Weight))
+			.saturating_add(DbWeight::get().writes(13 as Weight))
+	}
+	fn claim_rewards_for() -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(20 as Weight))
+			.saturating_add(DbWeight::get().writes(13 as Weight))
+	}
+}
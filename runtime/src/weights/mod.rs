@@ -1,4 +1,13 @@
 //! A list of the different weight modules for our runtime.
 #![allow(clippy::unnecessary_cast)]
 
-pub mod evm_accounts;
\ No newline at end of file
+pub mod evm_accounts;
+pub mod feeless;
+pub mod validator_set;
+pub mod social_token;
+pub mod bein_nft;
+pub mod auction_house;
+pub mod tips;
+pub mod groups;
+pub mod tx_pause;
+pub mod base_fee_target;
@@ -0,0 +1,31 @@
+//! Weights for `pallet_tips`.
+//!
+//! Not benchmarked: the base weights are estimates and the storage accesses are counted
+//! from the code. Replace this file with benchmark output once the pallet has benchmarks.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+use sp_std::marker::PhantomData;
+
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_tips::WeightInfo for WeightInfo<T> {
+	fn tip() -> Weight {
+		(65_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn prune_period() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn prune_creator_stats(n: u32) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((8_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads((n as Weight).saturating_add(1)))
+			.saturating_add(DbWeight::get().writes(n as Weight))
+	}
+}